@@ -0,0 +1,30 @@
+//! Off-chain helpers for Oridion clients.
+//! Addresses are derived with the same `shared.rs` helpers the program uses,
+//! so client and program derivation cannot drift.
+//! Every universe is seeded by a mint, use `NATIVE_MINT_SENTINEL` for the SOL universe.
+use anchor_lang::prelude::Pubkey;
+
+pub use oridion::shared::{planet_seed, star_seed};
+pub use oridion::variables::NATIVE_MINT_SENTINEL;
+pub use oridion::ID;
+
+/// Universe PDA of a mint.
+pub fn universe_address(mint: &Pubkey) -> Pubkey {
+    oridion::shared::get_universe_program_address(mint, &ID)
+}
+
+/// Planet PDA for a canonical (uppercase) planet name in the universe of a mint.
+pub fn planet_address(planet_name: &str, mint: &Pubkey) -> Pubkey {
+    oridion::shared::get_planet_program_address(planet_name, mint, &ID)
+}
+
+/// Star PDA for a universe star counter id.
+/// A star hop start uses the universe counter and the following ids.
+pub fn star_address(star_id: u64, mint: &Pubkey) -> Pubkey {
+    oridion::shared::get_star_program_address(star_id, mint, &ID)
+}
+
+/// Pool star PDA for a star pool index.
+pub fn pool_star_address(pool_index: u32, mint: &Pubkey) -> Pubkey {
+    oridion::shared::get_pool_star_program_address(pool_index, mint, &ID)
+}
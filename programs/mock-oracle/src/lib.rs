@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+declare_id!("9ybuZ2u9FVfs5zMaYe3WNvkn6XQJi3X22w4k2FMysgCm");
+
+//Pyth v2 price account layout (fields read by Oridion)
+pub const PRICE_ACCOUNT_LENGTH: usize = 240;
+const MAGIC: u32 = 0xa1b2c3d4;
+const VERSION: u32 = 2;
+const ACCOUNT_TYPE_PRICE: u32 = 3;
+const STATUS_TRADING: u32 = 1;
+const STATUS_UNKNOWN: u32 = 0;
+
+///-------------------------------------------------------------------///
+/// MOCK ORACLE
+/// Writes a SOL/USD price into a Pyth v2 compatible price account for local tests
+/// of Oridion USD fees. The price account is created by the client (owned by this
+/// program, at least PRICE_ACCOUNT_LENGTH bytes).
+///-------------------------------------------------------------------///
+#[program]
+pub mod mock_oracle {
+    use super::*;
+
+    pub fn set_price(ctx: Context<SetPrice>, price: i64, expo: i32, conf: u64, trading: bool, timestamp: i64) -> Result<()> {
+        let mut data = ctx.accounts.price_account.try_borrow_mut_data()?;
+        if data.len() < PRICE_ACCOUNT_LENGTH {
+            return Err(ErrorCode::PriceAccountTooSmall.into())
+        }
+        data[0..4].copy_from_slice(&MAGIC.to_le_bytes());
+        data[4..8].copy_from_slice(&VERSION.to_le_bytes());
+        data[8..12].copy_from_slice(&ACCOUNT_TYPE_PRICE.to_le_bytes());
+        data[20..24].copy_from_slice(&expo.to_le_bytes());
+        data[96..104].copy_from_slice(&timestamp.to_le_bytes());
+        data[208..216].copy_from_slice(&price.to_le_bytes());
+        data[216..224].copy_from_slice(&conf.to_le_bytes());
+        let status: u32 = if trading { STATUS_TRADING } else { STATUS_UNKNOWN };
+        data[224..228].copy_from_slice(&status.to_le_bytes());
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetPrice<'info> {
+    /// CHECK: raw Pyth v2 layout written by this program
+    #[account(mut, owner = crate::ID)]
+    pub price_account: UncheckedAccount<'info>,
+    pub authority: Signer<'info>,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Price account is smaller than the Pyth v2 price layout")]
+    PriceAccountTooSmall,
+}
@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+declare_id!("EnjYJ7nmxWe4JNGDKD25WpRQCQW82NgZAwqnytGb7vMP");
+
+///-------------------------------------------------------------------///
+/// MOCK TARGET
+/// Stand-in destination program for Oridion withdraw_and_invoke.
+/// Checks the withdrawn lamports arrived before the call and fails otherwise,
+/// which reverts the whole withdrawal.
+///-------------------------------------------------------------------///
+#[program]
+pub mod mock_target {
+    use super::*;
+
+    pub fn receive(ctx: Context<Receive>, expected_lamports: u64) -> Result<()> {
+        if ctx.accounts.destination.lamports() < expected_lamports {
+            return Err(ErrorCode::NotReceived.into())
+        }
+        msg!("Received {} lamports", expected_lamports);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct Receive<'info> {
+    /// CHECK: only the balance is read
+    pub destination: UncheckedAccount<'info>,
+}
+
+#[error_code]
+pub enum ErrorCode {
+    #[msg("Destination did not receive the expected lamports")]
+    NotReceived,
+}
@@ -22,17 +22,54 @@ use super::*;
 /// new anchor.BN(0).toArrayLike(Buffer)
 /// https://stackoverflow.com/questions/71807112/how-to-derive-pdas-with-multiple-seeds-in-anchor-rust
 ///
+/// CPI DEPOSITS
+/// Partner programs deposit with `oridion::cpi::new_comet` (`cpi` feature).
+/// See `programs/partner-example` for a minimal caller.
+/// The account order below is stable: creator, universe, planet, system_program, rent, oracle.
+/// - oracle is optional (only needed when the universe charges fees in USD)
+/// - new_comet_with_hops takes these accounts plus the comet that stores the hop credits
+///   (`CreateCometWithHops`, the creator pays its rent)
+/// - creator can be a PDA of the calling program (signed with `CpiContext::new_with_signer`)
+/// - creator must be system owned with no data, deposits are moved with a system transfer
+/// - universe must be the SOL universe (`get_universe_program_address(&NATIVE_MINT_SENTINEL, ..)`)
+///
 ///------------------------------------------------------------//
 #[derive(Accounts)]
 pub struct CreateComet<'info> {
     #[account(mut)]
     pub creator: Signer<'info>,
-    #[account(mut)]
+    #[account(mut, constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch, constraint = !universe.pa @ errors::ErrorCode::UniversePaused)]
     pub universe: Account<'info,Universe>,
-    #[account(mut)]
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse, constraint = !planet.closed @ errors::ErrorCode::PlanetClosed)]
     pub planet: Account<'info,Planet>,
     pub system_program: Program<'info,System>,
     pub rent: Sysvar<'info, Rent>,
+    /// CHECK: universe SOL/USD price account (required for USD fees, parsed in `fee_lamports`)
+    #[account(address = universe.or @ errors::ErrorCode::FeeOracleMismatch)]
+    pub oracle: Option<UncheckedAccount<'info>>,
+}
+
+//Deposit with a hop package: accounts of new_comet under `deposit`, plus the comet that stores
+//the bought credits (seeds: COMET_SEED, universe, id)
+#[derive(Accounts)]
+#[instruction(deposit_lamports: u64, hops: HopPackage)]
+pub struct CreateCometWithHops<'info> {
+    pub deposit: CreateComet<'info>,
+    #[account(init, payer = deposit.creator, space = Comet::LEN,
+        seeds = [COMET_SEED, deposit.universe.key().as_ref(), &hops.id.to_le_bytes()],
+        bump
+    )]
+    pub comet: Account<'info, Comet>,
+    pub system_program: Program<'info, System>,
+}
+
+//Close a comet and return its rent to the creator (remaining credits are forfeited)
+#[derive(Accounts)]
+pub struct CloseComet<'info> {
+    #[account(mut, close = creator, has_one = creator)]
+    pub comet: Account<'info, Comet>,
+    #[account(mut)]
+    pub creator: Signer<'info>,
 }
 
 //Constrain = The "creator public key" being passed during this update
@@ -41,9 +78,13 @@ pub struct CreateComet<'info> {
 //Hope to planet will always go from planet to planet
 #[derive(Accounts)]
 pub struct PlanetHop<'info> {
-    #[account(mut)]
+    #[account(mut, constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch, constraint = !universe.pa @ errors::ErrorCode::UniversePaused)]
+    pub universe: Account<'info,Universe>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::CometWrongUniverse)]
+    pub comet: Account<'info, Comet>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse, constraint = !to_planet.closed @ errors::ErrorCode::PlanetClosed)]
     pub to_planet: Account<'info,Planet>,
-    #[account(mut)]
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse)]
     pub from_planet: Account<'info,Planet>,
     #[account(mut, address = MANAGER_PUBKEY)]
     pub manager: Signer<'info>
@@ -51,24 +92,103 @@ pub struct PlanetHop<'info> {
 
 #[derive(Accounts)]
 pub struct WithdrawAccounts<'info> {
+    #[account(mut, constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info,Universe>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse)]
+    pub from_planet: Account<'info,Planet>,
     #[account(mut)]
+    pub destination: SystemAccount<'info>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub manager: Signer<'info>,
+    /// CHECK: universe SOL/USD price account (required for USD fees, parsed in `fee_lamports`)
+    #[account(address = universe.or @ errors::ErrorCode::FeeOracleMismatch)]
+    pub oracle: Option<UncheckedAccount<'info>>,
+}
+
+//Withdraw to a destination that may not exist yet (zero lamports)
+//The manager tops up the rent exempt minimum when the payout does not cover it
+#[derive(Accounts)]
+pub struct WithdrawToNewAccounts<'info> {
+    #[account(mut, constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info,Universe>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse)]
     pub from_planet: Account<'info,Planet>,
     #[account(mut)]
     pub destination: SystemAccount<'info>,
     #[account(mut, address = MANAGER_PUBKEY)]
-    pub manager: Signer<'info>
+    pub manager: Signer<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: universe SOL/USD price account (required for USD fees, parsed in `fee_lamports`)
+    #[account(address = universe.or @ errors::ErrorCode::FeeOracleMismatch)]
+    pub oracle: Option<UncheckedAccount<'info>>,
+}
+
+//Withdraw to an allowlisted destination of any owner
+#[derive(Accounts)]
+pub struct WithdrawToAllowlistedAccounts<'info> {
+    #[account(mut, constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info,Universe>,
+    #[account(has_one = universe,
+        seeds = [ALLOWLIST_SEED, universe.key().as_ref()],
+        bump,
+        constraint = allowlist.d.contains(destination.key) @ errors::ErrorCode::DestinationNotAllowlisted
+    )]
+    pub allowlist: Account<'info, Allowlist>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse)]
+    pub from_planet: Account<'info,Planet>,
+    /// CHECK: any owner, must be in the universe allowlist
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub manager: Signer<'info>,
+    /// CHECK: universe SOL/USD price account (required for USD fees, parsed in `fee_lamports`)
+    #[account(address = universe.or @ errors::ErrorCode::FeeOracleMismatch)]
+    pub oracle: Option<UncheckedAccount<'info>>,
+}
+
+//Withdraw, then CPI into an allowlisted program (atomic)
+//Target program accounts are passed through remaining accounts (none may be signers)
+#[derive(Accounts)]
+pub struct WithdrawAndInvokeAccounts<'info> {
+    #[account(mut, constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info,Universe>,
+    #[account(has_one = universe,
+        seeds = [ALLOWLIST_SEED, universe.key().as_ref()],
+        bump,
+        constraint = allowlist.pg.contains(target_program.key) @ errors::ErrorCode::ProgramNotAllowlisted,
+        constraint = destination.owner == &system_program::ID || allowlist.d.contains(destination.key) @ errors::ErrorCode::InvokeDestinationNotAllowed
+    )]
+    pub allowlist: Account<'info, Allowlist>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse)]
+    pub from_planet: Account<'info,Planet>,
+    /// CHECK: system owned or in the universe allowlist
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+    /// CHECK: must be executable and in the universe allowlist
+    #[account(constraint = target_program.executable @ errors::ErrorCode::InvokeTargetNotExecutable)]
+    pub target_program: UncheckedAccount<'info>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub manager: Signer<'info>,
+    /// CHECK: universe SOL/USD price account (required for USD fees, parsed in `fee_lamports`)
+    #[account(address = universe.or @ errors::ErrorCode::FeeOracleMismatch)]
+    pub oracle: Option<UncheckedAccount<'info>>,
 }
 
 //Star hop from Planet to Split stars
+//Star seeds come from the universe star counter
+//(saturating here, the handler fails with MathOverflow when the counter is exhausted)
 #[derive(Accounts)]
-#[instruction(star_one_id: String, star_two_id: String)]
 pub struct StarHopTwoStart<'info> {
-    #[account(mut)]
+    #[account(mut, constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch, constraint = !universe.pa @ errors::ErrorCode::UniversePaused)]
+    pub universe: Account<'info,Universe>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::CometWrongUniverse)]
+    pub comet: Account<'info, Comet>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse)]
     pub from_planet: Account<'info,Planet>,
     #[account(init, payer = manager, space = Star::LEN,
         seeds = [
             STAR_SEED_PRE,
-            star_one_id.as_ref(),
+            &star_seed(universe.sc, &universe.mt),
             STAR_SEED_POST
         ],
         bump
@@ -77,7 +197,7 @@ pub struct StarHopTwoStart<'info> {
     #[account(init, payer = manager, space = Star::LEN,
         seeds = [
             STAR_SEED_PRE,
-            star_two_id.as_ref(),
+            &star_seed(universe.sc.saturating_add(1), &universe.mt),
             STAR_SEED_POST
         ],
         bump
@@ -92,26 +212,33 @@ pub struct StarHopTwoStart<'info> {
 //Return from stars to destination planet
 #[derive(Accounts)]
 pub struct StarHopTwoEnd<'info> {
-    #[account(mut)]
+    #[account(mut, constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info,Universe>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse, constraint = !to_planet.closed @ errors::ErrorCode::PlanetClosed)]
     pub to_planet: Account<'info,Planet>,
-    #[account(mut, close = manager, has_one = manager, constraint = manager.key == &star_one.manager)]
+    #[account(mut, close = manager, has_one = manager, constraint = manager.key == &star_one.manager, constraint = !star_one.pooled @ errors::ErrorCode::StarIsPooled)]
     pub star_one: Account<'info, Star>,
-    #[account(mut, close = manager, has_one = manager, constraint = manager.key == &star_two.manager)]
+    #[account(mut, close = manager, has_one = manager, constraint = manager.key == &star_two.manager, constraint = !star_two.pooled @ errors::ErrorCode::StarIsPooled)]
     pub star_two: Account<'info, Star>,
     #[account(mut, address = MANAGER_PUBKEY)]
     pub manager: Signer<'info>
 }
 
 
+//Star seeds come from the universe star counter
+//(saturating here, the handler fails with MathOverflow when the counter is exhausted)
 #[derive(Accounts)]
-#[instruction(star_one_id: String, star_two_id: String, star_three_id: String )]
 pub struct StarHopThreeStart<'info> {
-    #[account(mut)]
+    #[account(mut, constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch, constraint = !universe.pa @ errors::ErrorCode::UniversePaused)]
+    pub universe: Account<'info,Universe>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::CometWrongUniverse)]
+    pub comet: Account<'info, Comet>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse)]
     pub from_planet: Account<'info,Planet>,
     #[account(init, payer = manager, space = Star::LEN,
         seeds = [
             STAR_SEED_PRE,
-            star_one_id.as_ref(),
+            &star_seed(universe.sc, &universe.mt),
             STAR_SEED_POST
         ],
         bump
@@ -121,7 +248,7 @@ pub struct StarHopThreeStart<'info> {
     #[account(init, payer = manager, space = Star::LEN,
         seeds = [
             STAR_SEED_PRE,
-            star_two_id.as_ref(),
+            &star_seed(universe.sc.saturating_add(1), &universe.mt),
             STAR_SEED_POST
         ],
         bump
@@ -131,7 +258,7 @@ pub struct StarHopThreeStart<'info> {
     #[account(init, payer = manager, space = Star::LEN,
         seeds = [
             STAR_SEED_PRE,
-            star_three_id.as_ref(),
+            &star_seed(universe.sc.saturating_add(2), &universe.mt),
             STAR_SEED_POST
         ],
         bump
@@ -145,27 +272,234 @@ pub struct StarHopThreeStart<'info> {
 
 #[derive(Accounts)]
 pub struct StarHopThreeEnd<'info> {
-    #[account(mut)]
+    #[account(mut, constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info,Universe>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse, constraint = !to_planet.closed @ errors::ErrorCode::PlanetClosed)]
     pub to_planet: Account<'info,Planet>,
-    #[account(mut, close = manager, has_one = manager, constraint = manager.key == &star_one.manager)]
+    #[account(mut, close = manager, has_one = manager, constraint = manager.key == &star_one.manager, constraint = !star_one.pooled @ errors::ErrorCode::StarIsPooled)]
     pub star_one: Account<'info, Star>,
-    #[account(mut, close = manager, has_one = manager, constraint = manager.key == &star_two.manager)]
+    #[account(mut, close = manager, has_one = manager, constraint = manager.key == &star_two.manager, constraint = !star_two.pooled @ errors::ErrorCode::StarIsPooled)]
     pub star_two: Account<'info, Star>,
-    #[account(mut, close = manager, has_one = manager, constraint = manager.key == &star_three.manager)]
+    #[account(mut, close = manager, has_one = manager, constraint = manager.key == &star_three.manager, constraint = !star_three.pooled @ errors::ErrorCode::StarIsPooled)]
     pub star_three: Account<'info, Star>,
     #[account(mut, address = MANAGER_PUBKEY)]
     pub manager: Signer<'info>
 }
 
+//Return from two stars to two different destination planets
+#[derive(Accounts)]
+pub struct StarHopTwoEndSplit<'info> {
+    #[account(mut, constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info,Universe>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse, constraint = !to_planet_one.closed @ errors::ErrorCode::PlanetClosed)]
+    pub to_planet_one: Account<'info,Planet>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse, constraint = !to_planet_two.closed @ errors::ErrorCode::PlanetClosed)]
+    pub to_planet_two: Account<'info,Planet>,
+    #[account(mut, close = manager, has_one = manager, constraint = manager.key == &star_one.manager, constraint = !star_one.pooled @ errors::ErrorCode::StarIsPooled)]
+    pub star_one: Account<'info, Star>,
+    #[account(mut, close = manager, has_one = manager, constraint = manager.key == &star_two.manager, constraint = !star_two.pooled @ errors::ErrorCode::StarIsPooled)]
+    pub star_two: Account<'info, Star>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub manager: Signer<'info>
+}
+
+//Return from three stars to two or three destination planets.
+//The third planet is optional when the stars are partitioned between two planets.
+#[derive(Accounts)]
+pub struct StarHopThreeEndSplit<'info> {
+    #[account(mut, constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info,Universe>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse, constraint = !to_planet_one.closed @ errors::ErrorCode::PlanetClosed)]
+    pub to_planet_one: Account<'info,Planet>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse, constraint = !to_planet_two.closed @ errors::ErrorCode::PlanetClosed)]
+    pub to_planet_two: Account<'info,Planet>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse, constraint = !to_planet_three.closed @ errors::ErrorCode::PlanetClosed)]
+    pub to_planet_three: Option<Account<'info,Planet>>,
+    #[account(mut, close = manager, has_one = manager, constraint = manager.key == &star_one.manager, constraint = !star_one.pooled @ errors::ErrorCode::StarIsPooled)]
+    pub star_one: Account<'info, Star>,
+    #[account(mut, close = manager, has_one = manager, constraint = manager.key == &star_two.manager, constraint = !star_two.pooled @ errors::ErrorCode::StarIsPooled)]
+    pub star_two: Account<'info, Star>,
+    #[account(mut, close = manager, has_one = manager, constraint = manager.key == &star_three.manager, constraint = !star_three.pooled @ errors::ErrorCode::StarIsPooled)]
+    pub star_three: Account<'info, Star>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub manager: Signer<'info>
+}
+
+//Recover a stranded star back to its origin (or a fallback) planet
+//Not closed here, pool stars are deactivated and other stars are exploded in the handler
+#[derive(Accounts)]
+pub struct RecoverStar<'info> {
+    #[account(mut, constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info,Universe>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse, constraint = !to_planet.closed @ errors::ErrorCode::PlanetClosed)]
+    pub to_planet: Account<'info,Planet>,
+    #[account(mut, has_one = manager,
+        constraint = star.in_use @ errors::ErrorCode::StarPoolStarNotActive,
+        constraint = star.mint == Pubkey::default() @ errors::ErrorCode::StarIsTokenStar //token stars use recover_token_star
+    )]
+    pub star: Account<'info, Star>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub manager: Signer<'info>
+}
+
+//Add a star to the end of the star pool
+#[derive(Accounts)]
+pub struct GrowStarPool<'info> {
+    #[account(mut, constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info,Universe>,
+    #[account(init, payer = manager, space = Star::LEN,
+        seeds = [
+            STAR_POOL_SEED_PRE,
+            &star_seed(universe.sp as u64, &universe.mt),
+            STAR_SEED_POST
+        ],
+        bump
+    )]
+    pub star: Account<'info, Star>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub manager: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+//Explode the last star of the star pool
+#[derive(Accounts)]
+pub struct ShrinkStarPool<'info> {
+    #[account(mut, constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info,Universe>,
+    #[account(mut, close = manager,
+        seeds = [
+            STAR_POOL_SEED_PRE,
+            &star_seed(universe.sp.saturating_sub(1) as u64, &universe.mt),
+            STAR_SEED_POST
+        ],
+        bump,
+        constraint = !star.in_use @ errors::ErrorCode::StarPoolStarUnavailable
+    )]
+    pub star: Account<'info, Star>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub manager: Signer<'info>
+}
+
+//Star pool hop from planet to two idle pool stars
+#[derive(Accounts)]
+pub struct StarPoolHopTwoStart<'info> {
+    #[account(mut, constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch, constraint = !universe.pa @ errors::ErrorCode::UniversePaused)]
+    pub universe: Account<'info,Universe>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::CometWrongUniverse)]
+    pub comet: Account<'info, Comet>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse)]
+    pub from_planet: Account<'info,Planet>,
+    #[account(mut, has_one = manager, constraint = star_one.pooled && !star_one.in_use @ errors::ErrorCode::StarPoolStarUnavailable)]
+    pub star_one: Account<'info, Star>,
+    #[account(mut, has_one = manager, constraint = star_two.pooled && !star_two.in_use @ errors::ErrorCode::StarPoolStarUnavailable)]
+    pub star_two: Account<'info, Star>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub manager: Signer<'info>
+}
+
+//Return from two active pool stars to destination planet
+#[derive(Accounts)]
+pub struct StarPoolHopTwoEnd<'info> {
+    #[account(mut, constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info,Universe>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse, constraint = !to_planet.closed @ errors::ErrorCode::PlanetClosed)]
+    pub to_planet: Account<'info,Planet>,
+    #[account(mut, has_one = manager, constraint = star_one.pooled && star_one.in_use @ errors::ErrorCode::StarPoolStarNotActive)]
+    pub star_one: Account<'info, Star>,
+    #[account(mut, has_one = manager, constraint = star_two.pooled && star_two.in_use @ errors::ErrorCode::StarPoolStarNotActive)]
+    pub star_two: Account<'info, Star>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub manager: Signer<'info>
+}
+
+//Star pool hop from planet to three idle pool stars
+#[derive(Accounts)]
+pub struct StarPoolHopThreeStart<'info> {
+    #[account(mut, constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch, constraint = !universe.pa @ errors::ErrorCode::UniversePaused)]
+    pub universe: Account<'info,Universe>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::CometWrongUniverse)]
+    pub comet: Account<'info, Comet>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse)]
+    pub from_planet: Account<'info,Planet>,
+    #[account(mut, has_one = manager, constraint = star_one.pooled && !star_one.in_use @ errors::ErrorCode::StarPoolStarUnavailable)]
+    pub star_one: Account<'info, Star>,
+    #[account(mut, has_one = manager, constraint = star_two.pooled && !star_two.in_use @ errors::ErrorCode::StarPoolStarUnavailable)]
+    pub star_two: Account<'info, Star>,
+    #[account(mut, has_one = manager, constraint = star_three.pooled && !star_three.in_use @ errors::ErrorCode::StarPoolStarUnavailable)]
+    pub star_three: Account<'info, Star>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub manager: Signer<'info>
+}
+
+//Return from three active pool stars to destination planet
+#[derive(Accounts)]
+pub struct StarPoolHopThreeEnd<'info> {
+    #[account(mut, constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info,Universe>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse, constraint = !to_planet.closed @ errors::ErrorCode::PlanetClosed)]
+    pub to_planet: Account<'info,Planet>,
+    #[account(mut, has_one = manager, constraint = star_one.pooled && star_one.in_use @ errors::ErrorCode::StarPoolStarNotActive)]
+    pub star_one: Account<'info, Star>,
+    #[account(mut, has_one = manager, constraint = star_two.pooled && star_two.in_use @ errors::ErrorCode::StarPoolStarNotActive)]
+    pub star_two: Account<'info, Star>,
+    #[account(mut, has_one = manager, constraint = star_three.pooled && star_three.in_use @ errors::ErrorCode::StarPoolStarNotActive)]
+    pub star_three: Account<'info, Star>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub manager: Signer<'info>
+}
+
+//Hop package bought with a deposit (credits per hop kind, stored on the comet)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct HopPackage {
+    pub id: u64, //Comet id (seed)
+    pub planet: u16, //Planet hops
+    pub star_two: u16, //Two star hops
+    pub star_three: u16, //Three star hops
+}
+
+//Prepaid hop credits of a deposit. Every hop start consumes one credit of its kind.
+#[account]
+pub struct Comet {
+    pub universe: Pubkey, //Universe
+    pub creator: Pubkey, //Rent payer (can close the comet)
+    pub id: u64, //Comet id (seed)
+    pub ph: u16, //Planet hop credits left
+    pub s2: u16, //Two star hop credits left
+    pub s3: u16, //Three star hop credits left
+    pub bump: u8, //Bump
+}
+impl Comet {
+    pub(crate) const LEN: usize = DISCRIMINATOR_LENGTH
+        + PUBLIC_KEY_LENGTH //Universe
+        + PUBLIC_KEY_LENGTH //Creator
+        + U64_LENGTH //Id
+        + U16_LENGTH //Planet hop credits
+        + U16_LENGTH //Two star hop credits
+        + U16_LENGTH //Three star hop credits
+        + U8_LENGTH; //Bump
+}
+
 #[account]
 pub struct Star {
-    pub id: String,
+    pub id: u64, //Universe star counter (pool index for pool stars)
     pub amount: u64,
-    pub manager: Pubkey
+    pub manager: Pubkey,
+    pub created: i64, //Star created (used for stranded star recovery)
+    pub origin: Pubkey, //Planet the star was split from
+    pub pooled: bool, //Long lived pool star
+    pub in_use: bool, //Star currently holds hop funds
+    pub mint: Pubkey, //Token mode mint (default pubkey = lamports)
+    pub bump: u8 //Bump (token stars sign vault transfers)
 }
 impl Star {
-    const LEN: usize = DISCRIMINATOR_LENGTH
-        + STRING_LENGTH_PREFIX + STAR_ID_LENGTH //Star ID (String)
+    pub(crate) const LEN: usize = DISCRIMINATOR_LENGTH
+        + STAR_ID_LENGTH //Star ID
         + LAMPORT_LENGTH
-        + PUBLIC_KEY_LENGTH; // Lamports.
+        + PUBLIC_KEY_LENGTH // Lamports.
+        + TIMESTAMP_LENGTH // Created
+        + PUBLIC_KEY_LENGTH // Origin planet
+        + BOOL_LENGTH // Pooled
+        + BOOL_LENGTH // In use
+        + PUBLIC_KEY_LENGTH // Mint
+        + U8_LENGTH; // Bump
 }
\ No newline at end of file
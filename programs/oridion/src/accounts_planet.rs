@@ -4,7 +4,7 @@ use super::*;
 
 ///-------------------------------------------------------------//
 /// PLANETS PDA
-/// SEED - name to bytes
+/// SEED - sha256 of the universe mint and the name (see shared.rs)
 /// https://solana.stackexchange.com/questions/2840/best-ways-to-store-long-string-as-pda-seed
 /// https://github.com/0xShuk/String-as-PDA/blob/main/lib.rs
 ///
@@ -19,7 +19,10 @@ use super::*;
 /// - BURU
 /// - ZENOS
 /// - ISOMA
+///
 /// ["ORNOPU","DORAZ","ANDORA","JEBU","BABALO","KOYOPA","VREDIA","BURU","ZENOS","ISOMA"]
+///
+/// Names are validated (length, charset, uppercase, duplicates) while deriving the seed.
 ///-------------------------------------------------------------//
 #[derive(Accounts)]
 #[instruction(name: String)]
@@ -30,7 +33,7 @@ pub struct CreatePlanet<'info> {
         space = Planet::LEN,
         seeds = [
             PLANET_PDA_SEED_PRE,
-            name.as_ref(),
+            &planet_name_seed(&name, &universe)?,
             PLANET_PDA_SEED_POST
         ],
         bump
@@ -45,15 +48,58 @@ pub struct CreatePlanet<'info> {
 }
 
 
-//1593840 is the minimum amount needed for planets to exist
+//Planet must only hold its rent exempt minimum (checked in the handler)
+//Planets of a token universe also pass the mint and their (empty) vault
 #[derive(Accounts)]
 pub struct DeletePlanet<'info> {
-    #[account(mut, close = creator, constraint = &planet.to_account_info().lamports() <= &1593840)]
+    #[account(mut, close = creator, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse, constraint = planet.sk == 0 @ errors::ErrorCode::PlanetHasStake)]
     pub planet: Account<'info, Planet>,
     #[account(mut)]
     pub universe: Account<'info, Universe>,
     #[account(mut, address = MANAGER_PUBKEY)]
     pub creator: Signer<'info>,
+    /// CHECK: universe mint (token universes only)
+    #[account(address = universe.mt @ errors::ErrorCode::UniverseMintMismatch)]
+    pub mint: Option<UncheckedAccount<'info>>,
+    /// CHECK: planet vault (token universes only), must be empty (checked in `assert_planet_vault_empty`)
+    pub vault: Option<UncheckedAccount<'info>>,
+}
+
+//Close a planet to new deposits and hops before retiring it
+#[derive(Accounts)]
+pub struct ClosePlanet<'info> {
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse)]
+    pub planet: Account<'info, Planet>,
+    #[account(constraint = !universe.pa @ errors::ErrorCode::UniversePaused)]
+    pub universe: Account<'info, Universe>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub creator: Signer<'info>,
+}
+
+//Sweep a closed planet into target planets (remaining accounts) and delete it
+//Planets of a token universe also pass the mint and their vault, emptied with token hops first
+#[derive(Accounts)]
+pub struct RetirePlanet<'info> {
+    #[account(mut, close = creator, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse, constraint = planet.closed @ errors::ErrorCode::PlanetNotClosed, constraint = planet.sk == 0 @ errors::ErrorCode::PlanetHasStake)]
+    pub planet: Account<'info, Planet>,
+    #[account(mut, constraint = !universe.pa @ errors::ErrorCode::UniversePaused)]
+    pub universe: Account<'info, Universe>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub creator: Signer<'info>,
+    /// CHECK: universe mint (token universes only)
+    #[account(address = universe.mt @ errors::ErrorCode::UniverseMintMismatch)]
+    pub mint: Option<UncheckedAccount<'info>>,
+    /// CHECK: planet vault (token universes only), must be empty (checked in `assert_planet_vault_empty`)
+    pub vault: Option<UncheckedAccount<'info>>,
+}
+
+//Move lamports between planets (remaining accounts) toward a target distribution
+#[derive(Accounts)]
+pub struct Rebalance<'info> {
+    #[account(mut, constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch, constraint = !universe.pa @ errors::ErrorCode::UniversePaused)]
+    pub universe: Account<'info, Universe>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub creator: Signer<'info>,
 }
 
 #[account]
@@ -63,6 +109,9 @@ pub struct Planet {
     pub created: i64, //Planet started
     pub bump: u8, // Bump
     pub visits: u64, //Visitors 
+    pub closed: bool, //Closed to new deposits and hops (retiring)
+    pub universe: Pubkey, //Universe (mint) the planet belongs to
+    pub sk: u64, //Staked lamports (principal in the planet stake account)
 }
 impl Planet {
     const LEN: usize = DISCRIMINATOR_LENGTH
@@ -70,5 +119,8 @@ impl Planet {
         + PUBLIC_KEY_LENGTH //PDA
         + TIMESTAMP_LENGTH // Created
         + U8_LENGTH //Bump
-        + U64_LENGTH; //Visitors
+        + U64_LENGTH //Visitors
+        + BOOL_LENGTH //Closed
+        + PUBLIC_KEY_LENGTH //Universe
+        + LAMPORT_LENGTH; //Staked
 }
\ No newline at end of file
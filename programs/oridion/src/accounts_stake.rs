@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{stake, sysvar};
+use super::*;
+
+///------------------------------------------------------------//
+/// NATIVE STAKING
+/// A fraction of idle planet lamports can be delegated to a native stake account.
+/// Each planet has at most one stake account (PDA of the planet), with the universe
+/// PDA as staker and withdrawer. The manager pays the stake account rent, the
+/// planet the principal. Rewards go to the universe (treasury) on withdraw.
+/// Lamports sent to the stake address before staking are kept and end up as rewards.
+///------------------------------------------------------------//
+
+#[derive(Accounts)]
+pub struct UpdateUniverseStakeFraction<'info> {
+    #[account(mut)]
+    pub universe: Account<'info, Universe>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub creator: Signer<'info>
+}
+
+//Create, fund and delegate the planet stake account
+#[derive(Accounts)]
+pub struct StakePlanet<'info> {
+    #[account(mut, constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info, Universe>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse, constraint = !planet.closed @ errors::ErrorCode::PlanetClosed)]
+    pub planet: Account<'info, Planet>,
+    /// CHECK: allocated and assigned to the stake program here (may already hold lamports)
+    #[account(mut, seeds = [STAKE_SEED, planet.key().as_ref()], bump)]
+    pub stake_account: UncheckedAccount<'info>,
+    /// CHECK: validated by the stake program
+    pub vote_account: UncheckedAccount<'info>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub manager: Signer<'info>,
+    pub clock: Sysvar<'info, Clock>,
+    pub rent: Sysvar<'info, Rent>,
+    /// CHECK: stake history sysvar
+    #[account(address = sysvar::stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+    /// CHECK: stake config account
+    #[account(address = STAKE_CONFIG_ID)]
+    pub stake_config: UncheckedAccount<'info>,
+    /// CHECK: native stake program
+    #[account(address = stake::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+//Start the cooldown so the stake can be withdrawn
+#[derive(Accounts)]
+pub struct DeactivatePlanetStake<'info> {
+    #[account(constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info, Universe>,
+    #[account(has_one = universe @ errors::ErrorCode::PlanetWrongUniverse)]
+    pub planet: Account<'info, Planet>,
+    /// CHECK: planet stake account (validated by the stake program)
+    #[account(mut, seeds = [STAKE_SEED, planet.key().as_ref()], bump)]
+    pub stake_account: UncheckedAccount<'info>,
+    #[account(address = MANAGER_PUBKEY)]
+    pub manager: Signer<'info>,
+    pub clock: Sysvar<'info, Clock>,
+    /// CHECK: native stake program
+    #[account(address = stake::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+}
+
+//Withdraw an inactive stake: principal to the planet, rewards to the universe, rent to the manager
+#[derive(Accounts)]
+pub struct WithdrawPlanetStake<'info> {
+    #[account(mut, constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info, Universe>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse)]
+    pub planet: Account<'info, Planet>,
+    /// CHECK: planet stake account (validated by the stake program)
+    #[account(mut, seeds = [STAKE_SEED, planet.key().as_ref()], bump)]
+    pub stake_account: UncheckedAccount<'info>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub manager: Signer<'info>,
+    pub clock: Sysvar<'info, Clock>,
+    /// CHECK: stake history sysvar
+    #[account(address = sysvar::stake_history::ID)]
+    pub stake_history: UncheckedAccount<'info>,
+    /// CHECK: native stake program
+    #[account(address = stake::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+}
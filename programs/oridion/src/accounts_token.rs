@@ -0,0 +1,402 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::spl_token::native_mint;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use super::*;
+
+///------------------------------------------------------------//
+/// TOKEN MODE
+/// Planets and stars hold SPL / Token-2022 tokens in their associated token account (vault).
+/// Vaults are owned by the planet/star PDA and moved with `transfer_checked`
+/// signed by the PDA seeds. The universe ledger and stats count token units and fees go to the
+/// universe treasury vault (associated token account of the universe). The planet cap does not apply.
+/// Mints with extensions that break conservation are rejected (see `assert_supported_mint`).
+///------------------------------------------------------------//
+
+//Create the vault of a planet for a mint (may already have been created through the ATA program)
+#[derive(Accounts)]
+pub struct CreatePlanetVault<'info> {
+    #[account(constraint = universe.mt == mint.key() @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info,Universe>,
+    #[account(has_one = universe @ errors::ErrorCode::PlanetWrongUniverse)]
+    pub planet: Account<'info,Planet>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(init_if_needed, payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = planet,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub creator: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+//Token deposit from the user's token account to the planet vault, fees go to the universe treasury vault
+#[derive(Accounts)]
+pub struct CreateTokenComet<'info> {
+    #[account(mut, constraint = universe.mt == mint.key() @ errors::ErrorCode::UniverseMintMismatch, constraint = !universe.pa @ errors::ErrorCode::UniversePaused)]
+    pub universe: Account<'info,Universe>,
+    pub creator: Signer<'info>,
+    #[account(has_one = universe @ errors::ErrorCode::PlanetWrongUniverse, constraint = !planet.closed @ errors::ErrorCode::PlanetClosed)]
+    pub planet: Account<'info,Planet>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, token::mint = mint, token::authority = creator, token::token_program = token_program)]
+    pub source: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, associated_token::mint = mint, associated_token::authority = planet, associated_token::token_program = token_program)]
+    pub planet_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, associated_token::mint = mint, associated_token::authority = universe, associated_token::token_program = token_program)]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+//Token deposit with a hop package (see CreateCometWithHops)
+#[derive(Accounts)]
+#[instruction(deposit: u64, hops: HopPackage)]
+pub struct CreateTokenCometWithHops<'info> {
+    pub deposit: CreateTokenComet<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(init, payer = payer, space = Comet::LEN,
+        seeds = [COMET_SEED, deposit.universe.key().as_ref(), &hops.id.to_le_bytes()],
+        bump
+    )]
+    pub comet: Account<'info, Comet>,
+    pub system_program: Program<'info, System>,
+}
+
+//Create the treasury vault of a universe (token fees)
+#[derive(Accounts)]
+pub struct CreateTreasuryVault<'info> {
+    #[account(constraint = universe.mt == mint.key() @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info,Universe>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(init_if_needed, payer = creator,
+        associated_token::mint = mint,
+        associated_token::authority = universe,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub creator: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct TokenPlanetHop<'info> {
+    #[account(mut, constraint = universe.mt == mint.key() @ errors::ErrorCode::UniverseMintMismatch, constraint = !universe.pa @ errors::ErrorCode::UniversePaused)]
+    pub universe: Account<'info,Universe>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::CometWrongUniverse)]
+    pub comet: Account<'info, Comet>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse, constraint = !to_planet.closed @ errors::ErrorCode::PlanetClosed)]
+    pub to_planet: Account<'info,Planet>,
+    #[account(mut, associated_token::mint = mint, associated_token::authority = to_planet, associated_token::token_program = token_program)]
+    pub to_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(has_one = universe @ errors::ErrorCode::PlanetWrongUniverse)]
+    pub from_planet: Account<'info,Planet>,
+    #[account(mut, associated_token::mint = mint, associated_token::authority = from_planet, associated_token::token_program = token_program)]
+    pub from_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub manager: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct TokenWithdrawAccounts<'info> {
+    #[account(mut, constraint = universe.mt == mint.key() @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info,Universe>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(has_one = universe @ errors::ErrorCode::PlanetWrongUniverse)]
+    pub from_planet: Account<'info,Planet>,
+    #[account(mut, associated_token::mint = mint, associated_token::authority = from_planet, associated_token::token_program = token_program)]
+    pub from_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, associated_token::mint = mint, associated_token::authority = universe, associated_token::token_program = token_program)]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = mint, token::token_program = token_program)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub manager: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+//Token withdraw to the destination owner's associated token account (created when missing)
+#[derive(Accounts)]
+pub struct TokenWithdrawToAtaAccounts<'info> {
+    #[account(mut, constraint = universe.mt == mint.key() @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info,Universe>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(has_one = universe @ errors::ErrorCode::PlanetWrongUniverse)]
+    pub from_planet: Account<'info,Planet>,
+    #[account(mut, associated_token::mint = mint, associated_token::authority = from_planet, associated_token::token_program = token_program)]
+    pub from_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, associated_token::mint = mint, associated_token::authority = universe, associated_token::token_program = token_program)]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: only used as the associated token account authority (wallet or PDA)
+    pub destination_owner: UncheckedAccount<'info>,
+    #[account(init_if_needed, payer = manager,
+        associated_token::mint = mint,
+        associated_token::authority = destination_owner,
+        associated_token::token_program = token_program
+    )]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub manager: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+//Token star hop from planet vault to two star vaults
+//Star seeds come from the universe star counter (same as lamport star hops)
+//Star vaults may already exist: star addresses are predictable, so anyone can create
+//their associated token accounts ahead of time. Existing vaults must be empty.
+#[derive(Accounts)]
+pub struct TokenStarHopTwoStart<'info> {
+    #[account(mut, constraint = universe.mt == mint.key() @ errors::ErrorCode::UniverseMintMismatch, constraint = !universe.pa @ errors::ErrorCode::UniversePaused)]
+    pub universe: Account<'info,Universe>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::CometWrongUniverse)]
+    pub comet: Account<'info, Comet>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(has_one = universe @ errors::ErrorCode::PlanetWrongUniverse)]
+    pub from_planet: Account<'info,Planet>,
+    #[account(mut, associated_token::mint = mint, associated_token::authority = from_planet, associated_token::token_program = token_program)]
+    pub from_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(init, payer = manager, space = Star::LEN,
+        seeds = [
+            STAR_SEED_PRE,
+            &star_seed(universe.sc, &universe.mt),
+            STAR_SEED_POST
+        ],
+        bump
+    )]
+    pub star_one: Account<'info, Star>,
+    #[account(init_if_needed, payer = manager, associated_token::mint = mint, associated_token::authority = star_one, associated_token::token_program = token_program,
+        constraint = star_one_vault.owner == star_one.key() && star_one_vault.mint == universe.mt @ errors::ErrorCode::StarVaultInvalid,
+        constraint = star_one_vault.amount == 0 @ errors::ErrorCode::StarVaultNotEmpty
+    )]
+    pub star_one_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(init, payer = manager, space = Star::LEN,
+        seeds = [
+            STAR_SEED_PRE,
+            &star_seed(universe.sc.saturating_add(1), &universe.mt),
+            STAR_SEED_POST
+        ],
+        bump
+    )]
+    pub star_two: Account<'info, Star>,
+    #[account(init_if_needed, payer = manager, associated_token::mint = mint, associated_token::authority = star_two, associated_token::token_program = token_program,
+        constraint = star_two_vault.owner == star_two.key() && star_two_vault.mint == universe.mt @ errors::ErrorCode::StarVaultInvalid,
+        constraint = star_two_vault.amount == 0 @ errors::ErrorCode::StarVaultNotEmpty
+    )]
+    pub star_two_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub manager: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+//Return from two star vaults to destination planet vault
+#[derive(Accounts)]
+pub struct TokenStarHopTwoEnd<'info> {
+    #[account(mut, constraint = universe.mt == mint.key() @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info,Universe>,
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse, constraint = !to_planet.closed @ errors::ErrorCode::PlanetClosed)]
+    pub to_planet: Account<'info,Planet>,
+    #[account(mut, associated_token::mint = mint, associated_token::authority = to_planet, associated_token::token_program = token_program)]
+    pub to_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, close = manager, has_one = manager, has_one = mint)]
+    pub star_one: Account<'info, Star>,
+    #[account(mut, associated_token::mint = mint, associated_token::authority = star_one, associated_token::token_program = token_program)]
+    pub star_one_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, close = manager, has_one = manager, has_one = mint)]
+    pub star_two: Account<'info, Star>,
+    #[account(mut, associated_token::mint = mint, associated_token::authority = star_two, associated_token::token_program = token_program)]
+    pub star_two_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub manager: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+//Token star hop from planet vault to three star vaults
+#[derive(Accounts)]
+pub struct TokenStarHopThreeStart<'info> {
+    #[account(mut, constraint = universe.mt == mint.key() @ errors::ErrorCode::UniverseMintMismatch, constraint = !universe.pa @ errors::ErrorCode::UniversePaused)]
+    pub universe: Account<'info,Universe>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::CometWrongUniverse)]
+    pub comet: Account<'info, Comet>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(has_one = universe @ errors::ErrorCode::PlanetWrongUniverse)]
+    pub from_planet: Account<'info,Planet>,
+    #[account(mut, associated_token::mint = mint, associated_token::authority = from_planet, associated_token::token_program = token_program)]
+    pub from_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(init, payer = manager, space = Star::LEN,
+        seeds = [
+            STAR_SEED_PRE,
+            &star_seed(universe.sc, &universe.mt),
+            STAR_SEED_POST
+        ],
+        bump
+    )]
+    pub star_one: Account<'info, Star>,
+    #[account(init_if_needed, payer = manager, associated_token::mint = mint, associated_token::authority = star_one, associated_token::token_program = token_program,
+        constraint = star_one_vault.owner == star_one.key() && star_one_vault.mint == universe.mt @ errors::ErrorCode::StarVaultInvalid,
+        constraint = star_one_vault.amount == 0 @ errors::ErrorCode::StarVaultNotEmpty
+    )]
+    pub star_one_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(init, payer = manager, space = Star::LEN,
+        seeds = [
+            STAR_SEED_PRE,
+            &star_seed(universe.sc.saturating_add(1), &universe.mt),
+            STAR_SEED_POST
+        ],
+        bump
+    )]
+    pub star_two: Account<'info, Star>,
+    #[account(init_if_needed, payer = manager, associated_token::mint = mint, associated_token::authority = star_two, associated_token::token_program = token_program,
+        constraint = star_two_vault.owner == star_two.key() && star_two_vault.mint == universe.mt @ errors::ErrorCode::StarVaultInvalid,
+        constraint = star_two_vault.amount == 0 @ errors::ErrorCode::StarVaultNotEmpty
+    )]
+    pub star_two_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(init, payer = manager, space = Star::LEN,
+        seeds = [
+            STAR_SEED_PRE,
+            &star_seed(universe.sc.saturating_add(2), &universe.mt),
+            STAR_SEED_POST
+        ],
+        bump
+    )]
+    pub star_three: Account<'info, Star>,
+    #[account(init_if_needed, payer = manager, associated_token::mint = mint, associated_token::authority = star_three, associated_token::token_program = token_program,
+        constraint = star_three_vault.owner == star_three.key() && star_three_vault.mint == universe.mt @ errors::ErrorCode::StarVaultInvalid,
+        constraint = star_three_vault.amount == 0 @ errors::ErrorCode::StarVaultNotEmpty
+    )]
+    pub star_three_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub manager: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+//Return from three star vaults to destination planet vault
+#[derive(Accounts)]
+pub struct TokenStarHopThreeEnd<'info> {
+    #[account(mut, constraint = universe.mt == mint.key() @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info,Universe>,
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse, constraint = !to_planet.closed @ errors::ErrorCode::PlanetClosed)]
+    pub to_planet: Account<'info,Planet>,
+    #[account(mut, associated_token::mint = mint, associated_token::authority = to_planet, associated_token::token_program = token_program)]
+    pub to_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, close = manager, has_one = manager, has_one = mint)]
+    pub star_one: Account<'info, Star>,
+    #[account(mut, associated_token::mint = mint, associated_token::authority = star_one, associated_token::token_program = token_program)]
+    pub star_one_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, close = manager, has_one = manager, has_one = mint)]
+    pub star_two: Account<'info, Star>,
+    #[account(mut, associated_token::mint = mint, associated_token::authority = star_two, associated_token::token_program = token_program)]
+    pub star_two_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, close = manager, has_one = manager, has_one = mint)]
+    pub star_three: Account<'info, Star>,
+    #[account(mut, associated_token::mint = mint, associated_token::authority = star_three, associated_token::token_program = token_program)]
+    pub star_three_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub manager: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+//Recover a stranded token star back to its origin (or a fallback) planet vault
+//The star vault and the star are closed (rent to the manager)
+#[derive(Accounts)]
+pub struct RecoverTokenStar<'info> {
+    #[account(mut, constraint = universe.mt == mint.key() @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info,Universe>,
+    #[account(mut)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, has_one = universe @ errors::ErrorCode::PlanetWrongUniverse, constraint = !to_planet.closed @ errors::ErrorCode::PlanetClosed)]
+    pub to_planet: Account<'info,Planet>,
+    #[account(mut, associated_token::mint = mint, associated_token::authority = to_planet, associated_token::token_program = token_program)]
+    pub to_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, close = manager, has_one = manager, has_one = mint,
+        constraint = star.in_use @ errors::ErrorCode::StarPoolStarNotActive
+    )]
+    pub star: Account<'info, Star>,
+    #[account(mut, associated_token::mint = mint, associated_token::authority = star, associated_token::token_program = token_program)]
+    pub star_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub manager: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+//Native SOL deposit into the wSOL universe (wrapped into the planet vault, the fee into the treasury vault)
+#[derive(Accounts)]
+pub struct CreateTokenCometSol<'info> {
+    #[account(mut, constraint = universe.mt == mint.key() @ errors::ErrorCode::UniverseMintMismatch, constraint = !universe.pa @ errors::ErrorCode::UniversePaused)]
+    pub universe: Account<'info,Universe>,
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(has_one = universe @ errors::ErrorCode::PlanetWrongUniverse, constraint = !planet.closed @ errors::ErrorCode::PlanetClosed)]
+    pub planet: Account<'info,Planet>,
+    #[account(address = native_mint::ID)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, associated_token::mint = mint, associated_token::authority = planet, associated_token::token_program = token_program)]
+    pub planet_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, associated_token::mint = mint, associated_token::authority = universe, associated_token::token_program = token_program)]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+//wSOL withdraw unwrapped to a native SOL destination
+//The unwrap account is created and closed in the same instruction
+#[derive(Accounts)]
+pub struct TokenWithdrawSolAccounts<'info> {
+    #[account(mut, constraint = universe.mt == mint.key() @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info,Universe>,
+    #[account(address = native_mint::ID)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(has_one = universe @ errors::ErrorCode::PlanetWrongUniverse)]
+    pub from_planet: Account<'info,Planet>,
+    #[account(mut, associated_token::mint = mint, associated_token::authority = from_planet, associated_token::token_program = token_program)]
+    pub from_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, associated_token::mint = mint, associated_token::authority = universe, associated_token::token_program = token_program)]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(init, payer = manager,
+        seeds = [WSOL_UNWRAP_SEED, from_planet.key().as_ref()],
+        bump,
+        token::mint = mint,
+        token::authority = from_planet,
+        token::token_program = token_program
+    )]
+    pub unwrap: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub destination: SystemAccount<'info>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub manager: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+//Token fees held by the universe treasury vault to any token account of the mint
+#[derive(Accounts)]
+pub struct SweepTokenTreasury<'info> {
+    #[account(mut, constraint = universe.mt == mint.key() @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info,Universe>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, associated_token::mint = mint, associated_token::authority = universe, associated_token::token_program = token_program)]
+    pub treasury_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = mint, token::token_program = token_program)]
+    pub destination: InterfaceAccount<'info, TokenAccount>,
+    #[account(address = MANAGER_PUBKEY)]
+    pub creator: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
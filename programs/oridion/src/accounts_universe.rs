@@ -1,16 +1,19 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
 use super::*;
 
 ///-------------------------------------------------------------//
 /// BIG BANG UNIVERSE PDA
+/// One universe per mint (`NATIVE_MINT_SENTINEL` for SOL)
 ///-------------------------------------------------------------//
 #[derive(Accounts)]
+#[instruction(mint: Pubkey)]
 pub struct BigBang<'info> {
     #[account(
         init,
         payer = creator,
         space = Universe::LEN,
-        seeds = [UNIVERSE_PDA_SEED],
+        seeds = [UNIVERSE_PDA_SEED, mint.as_ref()],
         bump
     )]
     pub universe: Account<'info, Universe>,
@@ -28,7 +31,170 @@ pub struct UpdateUniverseFee<'info> {
     pub creator: Signer<'info>
 }
 
+#[derive(Accounts)]
+pub struct UpdateUniverseFeeOracle<'info> {
+    #[account(mut)]
+    pub universe: Account<'info, Universe>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub creator: Signer<'info>
+}
+
+#[derive(Accounts)]
+pub struct UpdateUniverseStarTimeout<'info> {
+    #[account(mut)]
+    pub universe: Account<'info, Universe>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub creator: Signer<'info>
+}
+
+#[derive(Accounts)]
+pub struct UpdateUniverseSharePolicy<'info> {
+    #[account(mut)]
+    pub universe: Account<'info, Universe>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub creator: Signer<'info>
+}
+
+#[derive(Accounts)]
+pub struct UpdateUniverseStatsBucket<'info> {
+    #[account(mut)]
+    pub universe: Account<'info, Universe>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub creator: Signer<'info>
+}
+
+#[derive(Accounts)]
+pub struct UpdateUniversePause<'info> {
+    #[account(mut)]
+    pub universe: Account<'info, Universe>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub creator: Signer<'info>
+}
+
+#[derive(Accounts)]
+pub struct UpdateUniversePlanetCap<'info> {
+    #[account(mut)]
+    pub universe: Account<'info, Universe>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub creator: Signer<'info>
+}
+
+#[derive(Accounts)]
+pub struct UpdateUniverseDenominations<'info> {
+    #[account(mut)]
+    pub universe: Account<'info, Universe>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub creator: Signer<'info>
+}
+
+//Fees and staking rewards held by the universe (treasury) to any destination
+#[derive(Accounts)]
+pub struct SweepTreasury<'info> {
+    #[account(mut, constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info, Universe>,
+    /// CHECK: receives lamports only
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+    #[account(address = MANAGER_PUBKEY)]
+    pub creator: Signer<'info>
+}
+
+#[derive(Accounts)]
+pub struct CreateAllowlist<'info> {
+    #[account(
+        init,
+        payer = creator,
+        space = Allowlist::LEN,
+        seeds = [ALLOWLIST_SEED, universe.key().as_ref()],
+        bump
+    )]
+    pub allowlist: Account<'info, Allowlist>,
+    pub universe: Account<'info, Universe>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub creator: Signer<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateAllowlist<'info> {
+    #[account(mut, has_one = universe)]
+    pub allowlist: Account<'info, Allowlist>,
+    pub universe: Account<'info, Universe>,
+    #[account(mut, address = MANAGER_PUBKEY)]
+    pub creator: Signer<'info>
+}
+
+//Read-only. Planets are passed through remaining accounts.
+#[derive(Accounts)]
+pub struct ReportPlanets<'info> {
+    #[account(constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info, Universe>,
+}
+
+//Read-only. Planets and stars are passed through remaining accounts.
+#[derive(Accounts)]
+pub struct VerifyInvariants<'info> {
+    #[account(constraint = universe.mt == NATIVE_MINT_SENTINEL @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info, Universe>,
+}
+
+//Read-only. Planets and token stars are passed through remaining accounts, each followed by its vault.
+#[derive(Accounts)]
+pub struct VerifyTokenInvariants<'info> {
+    #[account(constraint = universe.mt == mint.key() @ errors::ErrorCode::UniverseMintMismatch)]
+    pub universe: Account<'info, Universe>,
+    pub mint: InterfaceAccount<'info, Mint>,
+}
+
+//Returned by verify invariants (return data)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct InvariantTotals {
+    pub universe: u64, //Universe lamports above rent
+    pub planets: u64, //Planet lamports above rent
+    pub stars: u64, //Lamports held by open stars
+    pub deposited: u64,
+    pub withdrawn: u64,
+    pub fees: u64,
+    pub staked: u64, //Planet principal held by stake accounts
+    pub rewards: u64, //Staking rewards collected into the universe
+    pub shortfall: u64, //Staked principal that did not come back
+    pub swept: u64, //Fees and rewards swept out of the universe
+}
+
+//Returned by verify token invariants (return data)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TokenInvariantTotals {
+    pub planets: u64, //Tokens held by planet vaults
+    pub stars: u64, //Tokens held by open star vaults
+    pub deposited: u64,
+    pub withdrawn: u64,
+    pub fees: u64,
+    pub withheld: u64, //Transfer fees withheld on hops
+    pub swept: u64, //Fees swept out of the treasury vault
+}
+
+//Returned by report planets (return data)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PlanetReport {
+    pub cap: u64, //Universe max planet balance (0 = no cap)
+    pub target: u64, //Equal share of the reported planet balances
+    pub planets: Vec<PlanetBalance>,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct PlanetBalance {
+    pub planet: Pubkey,
+    pub balance: u64, //Lamports above rent
+    pub over_target: bool,
+    pub over_cap: bool,
+}
+
+//One universe per mint, each with its own planets, fees, ledger and stats.
 //For privacy, we do not know how many comets are in the universe.
+//Aggregate stats (hops by type, bucketed deposit/withdraw totals) are kept for operations.
+//The exact ledger (td, tw, tf, ns) is needed for conservation checks and is public like the rest
+//of the account, so deposit sizes can be read from its changes (and from planet balances).
+//Bucketing the stats rounds what reporting tools show, it does not hide individual deposits.
 #[account]
 pub struct Universe {
     pub pda: Pubkey, //PDA
@@ -36,14 +202,43 @@ pub struct Universe {
     pub st: i64, //Universe started
     pub up: i64, //Last updated (used for comet random id)
     pub bp: u8, // Bump
-    pub cfe: u64, // Comet Fee in lamports
+    pub cfe: u64, // Comet Fee in lamports (USD micro-cents when fu)
     pub hpfe: u64, // Hop planet Fee in lamports
     pub hsfe2: u64, // Hop star Fee in lamports
     pub hsfe3: u64, // Hop star 3 Fee in lamports
-    pub wfe: u64 // Withdraw Fee in lamports
+    pub wfe: u64, // Withdraw Fee in lamports (USD micro-cents when fu)
+    pub sto: i64, // Star timeout in seconds (stranded star recovery)
+    pub sp: u32, // Star pool size
+    pub sc: u64, // Star counter (next star id)
+    pub smn: u16, // Min star share in basis points (manager weights)
+    pub smx: u16, // Max star share in basis points (manager weights)
+    pub td: u64, // Ledger: total deposited (lamports, token units in token universes)
+    pub tw: u64, // Ledger: total withdrawn (lamports, token units in token universes)
+    pub tf: u64, // Ledger: total fees collected (lamports, token units in token universes)
+    pub ns: u64, // Ledger: open stars (stars holding hop funds)
+    pub hp: u64, // Stats: planet hops
+    pub hs2: u64, // Stats: two star hops
+    pub hs3: u64, // Stats: three star hops
+    pub bk: u64, // Stats: bucket size in lamports (0 = exact)
+    pub sd: u64, // Stats: deposited lamports rounded to the bucket (running total)
+    pub sw: u64, // Stats: withdrawn lamports rounded to the bucket (running total)
+    pub mb: u64, // Max planet balance in lamports above rent (0 = no cap)
+    pub mt: Pubkey, // Mint (NATIVE_MINT_SENTINEL = SOL)
+    pub pa: bool, // Paused (no new deposits or hops)
+    pub sf: u16, // Max staked fraction of a planet in basis points (0 = staking disabled)
+    pub sk: u64, // Ledger: staked lamports (planet principal in stake accounts)
+    pub rw: u64, // Staking rewards collected into the universe
+    pub fu: bool, // Fees in USD micro-cents (converted with the oracle at charge time)
+    pub or: Pubkey, // Oracle SOL/USD price account (Pyth v2 layout)
+    pub os: i64, // Oracle max staleness in seconds
+    pub oc: u16, // Oracle max confidence in basis points of the price
+    pub sl: u64, // Ledger: staked lamports lost (stake withdrawn short of its principal)
+    pub ts: u64, // Ledger: fees and rewards swept out of the universe
+    pub tl: u64, // Ledger: token units withheld by Token-2022 transfer fees on hops (between vaults)
+    pub dn: Vec<u64>, // Allowed deposit amounts (empty = any amount)
 }
 impl Universe {
-    const LEN: usize = DISCRIMINATOR_LENGTH
+    pub(crate) const LEN: usize = DISCRIMINATOR_LENGTH
         + PUBLIC_KEY_LENGTH //Universe PDA
         + PLANETS_VEC_LENGTH // Planets vector (planets count can be counted here!)
         + TIMESTAMP_LENGTH // Universe started
@@ -53,5 +248,48 @@ impl Universe {
         + LAMPORT_LENGTH // Hop planet fee
         + LAMPORT_LENGTH // Hop star2 fee
         + LAMPORT_LENGTH // Hop star3 fee
-        + LAMPORT_LENGTH; // Withdraw fee
-}
\ No newline at end of file
+        + LAMPORT_LENGTH // Withdraw fee
+        + TIMESTAMP_LENGTH // Star timeout
+        + U32_LENGTH // Star pool size
+        + U64_LENGTH // Star counter
+        + U16_LENGTH // Min star share
+        + U16_LENGTH // Max star share
+        + LAMPORT_LENGTH // Total deposited
+        + LAMPORT_LENGTH // Total withdrawn
+        + LAMPORT_LENGTH // Total fees
+        + U64_LENGTH // Open stars
+        + U64_LENGTH // Planet hops
+        + U64_LENGTH // Two star hops
+        + U64_LENGTH // Three star hops
+        + LAMPORT_LENGTH // Stats bucket
+        + LAMPORT_LENGTH // Bucketed deposited
+        + LAMPORT_LENGTH // Bucketed withdrawn
+        + LAMPORT_LENGTH // Max planet balance
+        + PUBLIC_KEY_LENGTH // Mint
+        + BOOL_LENGTH // Paused
+        + U16_LENGTH // Stake fraction
+        + LAMPORT_LENGTH // Staked
+        + LAMPORT_LENGTH // Staking rewards
+        + BOOL_LENGTH // USD fees
+        + PUBLIC_KEY_LENGTH // Oracle
+        + TIMESTAMP_LENGTH // Oracle max staleness
+        + U16_LENGTH // Oracle max confidence
+        + LAMPORT_LENGTH // Stake shortfall
+        + LAMPORT_LENGTH // Swept treasury
+        + U64_LENGTH // Transfer fees withheld
+        + STRING_LENGTH_PREFIX + U64_LENGTH * MAX_DENOMINATIONS; // Deposit denominations
+}
+//Withdraw destinations that may be program owned (PDAs, stake accounts, ...)
+//and programs that withdraw and invoke may call
+#[account]
+pub struct Allowlist {
+    pub universe: Pubkey, //Universe
+    pub d: Vec<Pubkey>, //Allowlisted destinations
+    pub pg: Vec<Pubkey>, //Allowlisted invoke programs
+}
+impl Allowlist {
+    pub(crate) const LEN: usize = DISCRIMINATOR_LENGTH
+        + PUBLIC_KEY_LENGTH // Universe
+        + STRING_LENGTH_PREFIX + PUBLIC_KEY_LENGTH * MAX_ALLOWLIST_DESTINATIONS // Destinations
+        + STRING_LENGTH_PREFIX + PUBLIC_KEY_LENGTH * MAX_ALLOWLIST_PROGRAMS; // Programs
+}
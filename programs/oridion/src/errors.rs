@@ -12,4 +12,136 @@ pub enum ErrorCode {
     PlanetNotEnoughFundsError,
     #[msg("Star split calculations do not add up!")]
     StarHopCalculationError,
+    #[msg("Destination planets must be unique")]
+    HopErrorPlanetsMustBeUnique,
+    #[msg("Every star must land on a supplied planet and every planet must receive a star")]
+    HopErrorInvalidLanding,
+    #[msg("Star timeout must be greater than zero")]
+    InvalidStarTimeout,
+    #[msg("Star cannot be recovered before the universe star timeout")]
+    StarRecoveryTimeoutNotReached,
+    #[msg("Star can only be recovered to its origin planet or a registered planet")]
+    StarRecoveryInvalidPlanet,
+    #[msg("Star pool is empty")]
+    StarPoolEmpty,
+    #[msg("Star is not an idle pool star")]
+    StarPoolStarUnavailable,
+    #[msg("Star is not an active pool star")]
+    StarPoolStarNotActive,
+    #[msg("Pool stars must use the star pool hop instructions")]
+    StarIsPooled,
+    #[msg("Share policy must satisfy 0 < min <= max <= 10000 basis points")]
+    InvalidSharePolicy,
+    #[msg("Split weights must be one per star, within the share policy and add up to 10000 basis points")]
+    InvalidSplitWeights,
+    #[msg("Debit would leave the account below its rent exempt minimum")]
+    RentFloorShortfall,
+    #[msg("Arithmetic overflow")]
+    MathOverflow,
+    #[msg("Invariant accounts must be planets or stars owned by the program")]
+    InvariantAccountNotOwned,
+    #[msg("Invariant accounts must not be repeated")]
+    InvariantDuplicateAccount,
+    #[msg("Planet is not registered in the universe")]
+    InvariantPlanetNotRegistered,
+    #[msg("Every registered planet and open star must be supplied")]
+    InvariantMissingAccounts,
+    #[msg("Planet and star balances do not match the universe ledger")]
+    InvariantLedgerMismatch,
+    #[msg("Planet is closed to new deposits and hops")]
+    PlanetClosed,
+    #[msg("Planet must be closed before it can be retired")]
+    PlanetNotClosed,
+    #[msg("Retire targets must be open, registered, writable planets other than the retiring planet")]
+    PlanetRetireInvalidTarget,
+    #[msg("Planet name must be 1 to 40 characters")]
+    PlanetNameInvalidLength,
+    #[msg("Planet name must be uppercase (canonical form)")]
+    PlanetNameNotCanonical,
+    #[msg("Planet name may only contain A-Z and 0-9")]
+    PlanetNameInvalidCharacters,
+    #[msg("Planet already exists in the universe")]
+    PlanetAlreadyExists,
+    #[msg("Universe planet registry is full")]
+    PlanetRegistryFull,
+    #[msg("Planet balance cap exceeded")]
+    PlanetBalanceCapExceeded,
+    #[msg("Reported accounts must be unique registered planets")]
+    PlanetReportInvalidAccount,
+    #[msg("Rebalance needs at least two open, registered, writable and unique planets")]
+    RebalanceInvalidPlanet,
+    #[msg("Rebalance weights must match the planets and add up to 10000")]
+    RebalanceInvalidWeights,
+    #[msg("Token stars hold tokens, not lamports")]
+    StarIsTokenStar,
+    #[msg("Mint has an extension that is not supported in token mode")]
+    UnsupportedMintExtension,
+    #[msg("Universe does not match the mint of this instruction")]
+    UniverseMintMismatch,
+    #[msg("Universe is paused")]
+    UniversePaused,
+    #[msg("Planet belongs to another universe")]
+    PlanetWrongUniverse,
+    #[msg("Destination is not allowlisted")]
+    DestinationNotAllowlisted,
+    #[msg("Allowlist is full")]
+    AllowlistFull,
+    #[msg("Destination is already allowlisted")]
+    AllowlistEntryExists,
+    #[msg("Program is not allowlisted")]
+    ProgramNotAllowlisted,
+    #[msg("Invoke target must be an executable program")]
+    InvokeTargetNotExecutable,
+    #[msg("Invoke accounts cannot be signers")]
+    InvokeSignerNotAllowed,
+    #[msg("Invoke destination must be system owned or allowlisted")]
+    InvokeDestinationNotAllowed,
+    #[msg("Invoke destination must be one of the callback accounts")]
+    InvokeDestinationMissing,
+    #[msg("Stake fraction must be at most 10000 basis points")]
+    InvalidStakeFraction,
+    #[msg("Staking is disabled for this universe")]
+    StakingDisabled,
+    #[msg("Stake amount must be above zero and within the universe stake fraction of the planet")]
+    StakeAmountInvalid,
+    #[msg("Planet already has a stake account")]
+    PlanetAlreadyStaked,
+    #[msg("Planet has no stake")]
+    PlanetNotStaked,
+    #[msg("Planet has staked lamports")]
+    PlanetHasStake,
+    #[msg("USD fees need an oracle, a staleness above zero and a confidence of at most 10000 basis points")]
+    InvalidFeeOracle,
+    #[msg("USD fees need the universe oracle account")]
+    FeeOracleMissing,
+    #[msg("Oracle account does not match the universe oracle")]
+    FeeOracleMismatch,
+    #[msg("Oracle account is not a trading Pyth v2 price account")]
+    FeeOracleInvalidAccount,
+    #[msg("Oracle price is stale")]
+    FeeOracleStale,
+    #[msg("Oracle price confidence interval is too wide")]
+    FeeOracleConfidence,
+    #[msg("Fee must be lower than the amount")]
+    FeeExceedsAmount,
+    #[msg("Sweep amount must be positive and within the collected fees and rewards")]
+    SweepExceedsTreasury,
+    #[msg("Comet belongs to another universe")]
+    CometWrongUniverse,
+    #[msg("Comet has no hop credits left for this hop")]
+    NoHopCredits,
+    #[msg("Star vault must be the star's associated token account for the universe mint")]
+    StarVaultInvalid,
+    #[msg("Star vault already holds tokens")]
+    StarVaultNotEmpty,
+    #[msg("Deposit amount is not one of the universe denominations")]
+    DepositNotADenomination,
+    #[msg("Denominations must be above zero, strictly increasing and at most 8")]
+    InvalidDenominations,
+    #[msg("Invariant vaults must be the associated token account of the preceding planet or star")]
+    InvariantVaultInvalid,
+    #[msg("Planets of a token universe need their mint and vault to be deleted")]
+    PlanetVaultRequired,
+    #[msg("Planet vault still holds tokens")]
+    PlanetVaultNotEmpty,
 }
\ No newline at end of file
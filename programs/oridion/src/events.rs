@@ -0,0 +1,225 @@
+use anchor_lang::prelude::*;
+
+///-------------------------------------------------------------//
+/// EVENTS
+/// Emitted on every state transition so indexers do not need to diff balances.
+/// Privacy sensitive fields (amounts, planet names, destinations) are only
+/// included when the program is built with the `event-details` feature.
+///-------------------------------------------------------------//
+
+#[event]
+pub struct UniverseCreated {
+    pub universe: Pubkey,
+    pub mint: Pubkey,
+    pub started: i64,
+}
+
+#[event]
+pub struct FeesUpdated {
+    pub universe: Pubkey,
+    pub comet_fee: u64,
+    pub hop_planet_fee: u64,
+    pub hop_star_fee2: u64,
+    pub hop_star_fee3: u64,
+    pub withdraw_fee: u64,
+}
+
+#[event]
+pub struct FeeOracleUpdated {
+    pub universe: Pubkey,
+    pub usd: bool,
+    pub oracle: Pubkey,
+    pub max_staleness: i64,
+    pub max_confidence: u16,
+}
+
+#[event]
+pub struct StarTimeoutUpdated {
+    pub universe: Pubkey,
+    pub star_timeout: i64,
+}
+
+#[event]
+pub struct SharePolicyUpdated {
+    pub universe: Pubkey,
+    pub min_share_bps: u16,
+    pub max_share_bps: u16,
+}
+
+#[event]
+pub struct StatsBucketUpdated {
+    pub universe: Pubkey,
+    pub bucket: u64,
+}
+
+#[event]
+pub struct UniversePauseUpdated {
+    pub universe: Pubkey,
+    pub paused: bool,
+}
+
+#[event]
+pub struct AllowlistUpdated {
+    pub universe: Pubkey,
+    pub destination: Pubkey,
+    pub allowed: bool,
+}
+
+#[event]
+pub struct ProgramAllowlistUpdated {
+    pub universe: Pubkey,
+    pub program: Pubkey,
+    pub allowed: bool,
+}
+
+#[event]
+pub struct WithdrawnAndInvoked {
+    pub from_planet: Pubkey,
+    pub program: Pubkey,
+    #[cfg(feature = "event-details")]
+    pub destination: Pubkey,
+    #[cfg(feature = "event-details")]
+    pub amount: u64,
+}
+
+#[event]
+pub struct TreasurySwept {
+    pub universe: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct StakeFractionUpdated {
+    pub universe: Pubkey,
+    pub fraction: u16,
+}
+
+#[event]
+pub struct PlanetStaked {
+    pub planet: Pubkey,
+    #[cfg(feature = "event-details")]
+    pub amount: u64,
+}
+
+#[event]
+pub struct PlanetStakeDeactivated {
+    pub planet: Pubkey,
+}
+
+#[event]
+pub struct PlanetStakeWithdrawn {
+    pub planet: Pubkey,
+    #[cfg(feature = "event-details")]
+    pub amount: u64,
+    #[cfg(feature = "event-details")]
+    pub rewards: u64,
+    #[cfg(feature = "event-details")]
+    pub shortfall: u64,
+}
+
+#[event]
+pub struct PlanetCapUpdated {
+    pub universe: Pubkey,
+    pub cap: u64,
+}
+
+#[event]
+pub struct PlanetCreated {
+    pub planet: Pubkey,
+    #[cfg(feature = "event-details")]
+    pub name: String,
+}
+
+#[event]
+pub struct PlanetDeleted {
+    pub planet: Pubkey,
+    #[cfg(feature = "event-details")]
+    pub name: String,
+}
+
+#[event]
+pub struct PlanetClosed {
+    pub planet: Pubkey,
+}
+
+#[event]
+pub struct PlanetRetired {
+    pub planet: Pubkey,
+    pub to_planets: Vec<Pubkey>,
+    #[cfg(feature = "event-details")]
+    pub name: String,
+    #[cfg(feature = "event-details")]
+    pub amount: u64,
+}
+
+#[event]
+pub struct PlanetsRebalanced {
+    pub planets: Vec<Pubkey>,
+    #[cfg(feature = "event-details")]
+    pub amount: u64,
+}
+
+#[event]
+pub struct DenominationsUpdated {
+    pub universe: Pubkey,
+    pub denominations: Vec<u64>,
+}
+
+#[event]
+pub struct CometClosed {
+    pub comet: Pubkey,
+}
+
+#[event]
+pub struct CometCreated {
+    pub planet: Pubkey,
+    #[cfg(feature = "event-details")]
+    pub amount: u64,
+}
+
+#[event]
+pub struct PlanetHopped {
+    pub from_planet: Pubkey,
+    pub to_planet: Pubkey,
+    #[cfg(feature = "event-details")]
+    pub amount: u64,
+}
+
+#[event]
+pub struct StarHopStarted {
+    pub from_planet: Pubkey,
+    pub stars: Vec<Pubkey>,
+    #[cfg(feature = "event-details")]
+    pub amounts: Vec<u64>,
+}
+
+#[event]
+pub struct StarHopEnded {
+    pub to_planets: Vec<Pubkey>,
+    pub stars: Vec<Pubkey>,
+    #[cfg(feature = "event-details")]
+    pub amount: u64,
+}
+
+#[event]
+pub struct StarRecovered {
+    pub star: Pubkey,
+    pub to_planet: Pubkey,
+    #[cfg(feature = "event-details")]
+    pub amount: u64,
+}
+
+#[event]
+pub struct StarPoolResized {
+    pub size: u32,
+}
+
+#[event]
+pub struct Withdrawn {
+    pub from_planet: Pubkey,
+    #[cfg(feature = "event-details")]
+    pub destination: Pubkey,
+    #[cfg(feature = "event-details")]
+    pub amount: u64,
+}
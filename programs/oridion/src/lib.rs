@@ -1,61 +1,330 @@
-mod variables;
+pub mod variables;
 mod errors;
 mod accounts_universe;
 mod accounts_comet;
 mod accounts_planet;
-mod shared;
+mod accounts_token;
+mod accounts_stake;
+pub mod shared;
+mod events;
 
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::system_instruction;
+use anchor_lang::system_program;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::stake;
+use anchor_lang::solana_program::stake::state::{Authorized, Lockup, StakeStateV2};
+use anchor_spl::token_interface;
+use anchor_lang::Discriminator;
 use variables::*;
 use shared::*;
 use accounts_universe::*;
 use accounts_comet::*;
+pub use accounts_comet::HopPackage;
 use accounts_planet::*;
+use accounts_token::*;
+use accounts_stake::*;
+use events::*;
 
 //declare_id!("6ihF5TkmwWKPJfxDWJoA6f6EuLYqvPKdMbj9ohJ6n7kg");
 declare_id!("33J2bC6ZYvg8Y77MWULZEWpWRkxCoM2aziZ5f67dwBXi");
 
 #[program]
 pub mod oridion {
-    use solana_program::instruction::Instruction;
     use super::*;
 
     ///-------------------------------------------------------------------///
     /// UNIVERSE 
-    /// Main initialization function. Create universe and will only be called one time.
+    /// Main initialization function. Creates the universe of a mint, called once per mint.
+    /// - mint: token mint or NATIVE_MINT_SENTINEL for SOL
     ///-------------------------------------------------------------------///
-    pub fn bang(ctx: Context<BigBang>) -> Result<()> {
+    pub fn bang(ctx: Context<BigBang>, mint: Pubkey) -> Result<()> {
         let clock: Clock = Clock::get().unwrap();
         let universe: &mut Account<Universe> = &mut ctx.accounts.universe;
         universe.bp = ctx.bumps.universe; // store bump seed in `Counter` account
         universe.p = Vec::<String>::new();
         universe.st = clock.unix_timestamp;
         universe.up = clock.unix_timestamp; //must set this here as well for random comet id
-        universe.cfe = 0; //Comet fee Lamports (0) - Starts at 0 cents (when solana is $100 per 1)
-        universe.hpfe = 1000000; //Hop planet lamports (5000000) - Starts at .50 cents (when solana is $100 per 1)
-        universe.hsfe2 = 2000000; //Hop star lamports (7000000) - Starts at .70 cents (when solana is $100 per 1)
-        universe.hsfe3 = 3000000; //Hop star lamports (7000000) - Starts at .70 cents (when solana is $100 per 1)
-        universe.wfe = 0; //Withdraw Lamports (0) - no withdraw fee - Starts at .0 cents (when solana is $100 per 1)
-        let(pda, _bump_seed) = Pubkey::find_program_address(&[UNIVERSE_PDA_SEED], &ctx.program_id);
-        universe.pda = pda;
-        //msg!("== BIG BANG! ==");
+        universe.cfe = 0; //Comet fee lamports (0) - no comet fee
+        universe.hpfe = 1000000; //Hop planet lamports
+        universe.hsfe2 = 2000000; //Hop star lamports
+        universe.hsfe3 = 3000000; //Hop star lamports
+        universe.wfe = 0; //Withdraw lamports (0) - no withdraw fee
+        universe.sto = DEFAULT_STAR_TIMEOUT; //Seconds before a stranded star can be recovered
+        universe.smn = DEFAULT_MIN_SHARE_BPS; //Smallest share a star can receive with manager weights
+        universe.smx = DEFAULT_MAX_SHARE_BPS; //Largest share a star can receive with manager weights
+        universe.mt = mint;
+        universe.pda = get_universe_program_address(&mint, ctx.program_id);
+
+        emit!(UniverseCreated {
+            universe: ctx.accounts.universe.key(),
+            mint,
+            started: clock.unix_timestamp,
+        });
         Ok(())
     }
 
     /// -------------------------------------------------------------------///
     /// UPDATE FEE
+    /// Lamports, or USD micro-cents (1e-8 USD) when the universe uses USD fees.
+    /// The comet fee is kept from deposits and the withdraw fee from withdrawals (both default to 0).
+    /// Collected fees stay in the universe until the manager sweeps them (see sweep_treasury).
     ///-------------------------------------------------------------------///
     pub fn update_fee(ctx: Context<UpdateUniverseFee>, comet_fee: u32, hop_planet_fee: u32, hop_star_fee2: u32, hop_star_fee3: u32, withdraw_fee: u32) -> Result<()> {
         let clock: Clock = Clock::get().unwrap();
         let universe: &mut Account<Universe> = &mut ctx.accounts.universe;
         universe.up = clock.unix_timestamp; //must set this here as well for random comet id
-        universe.cfe = comet_fee as u64; //Lamports - Starts at .50 cents (when solana is $100 per 1)
-        universe.hpfe = hop_planet_fee as u64; //Lamports - Starts at .50 cents (when solana is $100 per 1)
-        universe.hsfe2 = hop_star_fee2 as u64; //Lamports - Starts at .50 cents (when solana is $100 per 1)
-        universe.hsfe3 = hop_star_fee3 as u64; //Lamports - Starts at .50 cents (when solana is $100 per 1)
-        universe.wfe = withdraw_fee as u64; //Lamports - Starts at .50 cents (when solana is $100 per 1)
-        //msg!("== FEE UPDATED ==");
+        universe.cfe = comet_fee as u64; //Lamports, or USD micro-cents in USD mode
+        universe.hpfe = hop_planet_fee as u64;
+        universe.hsfe2 = hop_star_fee2 as u64;
+        universe.hsfe3 = hop_star_fee3 as u64;
+        universe.wfe = withdraw_fee as u64;
+
+        emit!(FeesUpdated {
+            universe: universe.key(),
+            comet_fee: comet_fee as u64,
+            hop_planet_fee: hop_planet_fee as u64,
+            hop_star_fee2: hop_star_fee2 as u64,
+            hop_star_fee3: hop_star_fee3 as u64,
+            withdraw_fee: withdraw_fee as u64,
+        });
+        Ok(())
+    }
+
+    /// -------------------------------------------------------------------///
+    /// UPDATE FEE ORACLE
+    /// Switches fees between lamports and USD micro-cents converted at charge time
+    /// with a SOL/USD price account (Pyth v2 layout).
+    /// - max_staleness: seconds since the last price update
+    /// - max_confidence: confidence interval in basis points of the price
+    ///-------------------------------------------------------------------///
+    pub fn update_fee_oracle(ctx: Context<UpdateUniverseFeeOracle>, usd: bool, oracle: Pubkey, max_staleness: i64, max_confidence: u16) -> Result<()> {
+        //SOL/USD prices only convert fees of the SOL universe
+        if usd && (oracle == Pubkey::default() || max_staleness <= 0 || max_confidence > BPS_TOTAL || ctx.accounts.universe.mt != NATIVE_MINT_SENTINEL) {
+            return Err(errors::ErrorCode::InvalidFeeOracle.into())
+        }
+        let universe: &mut Account<Universe> = &mut ctx.accounts.universe;
+        universe.fu = usd;
+        universe.or = oracle;
+        universe.os = max_staleness;
+        universe.oc = max_confidence;
+
+        emit!(FeeOracleUpdated {
+            universe: universe.key(),
+            usd,
+            oracle,
+            max_staleness,
+            max_confidence,
+        });
+        Ok(())
+    }
+
+    /// -------------------------------------------------------------------///
+    /// UPDATE STAR TIMEOUT
+    /// Seconds a star must be alive before it can be recovered.
+    ///-------------------------------------------------------------------///
+    pub fn update_star_timeout(ctx: Context<UpdateUniverseStarTimeout>, star_timeout: i64) -> Result<()> {
+        if star_timeout <= 0 {
+            return Err(errors::ErrorCode::InvalidStarTimeout.into())
+        }
+        let universe: &mut Account<Universe> = &mut ctx.accounts.universe;
+        universe.sto = star_timeout;
+
+        emit!(StarTimeoutUpdated {
+            universe: universe.key(),
+            star_timeout,
+        });
+        Ok(())
+    }
+
+    /// -------------------------------------------------------------------///
+    /// UPDATE SHARE POLICY
+    /// Min and max share (basis points) a star can receive when the manager supplies split weights.
+    ///-------------------------------------------------------------------///
+    pub fn update_share_policy(ctx: Context<UpdateUniverseSharePolicy>, min_share_bps: u16, max_share_bps: u16) -> Result<()> {
+        if min_share_bps == 0 || min_share_bps > max_share_bps || max_share_bps > BPS_TOTAL {
+            return Err(errors::ErrorCode::InvalidSharePolicy.into())
+        }
+        let universe: &mut Account<Universe> = &mut ctx.accounts.universe;
+        universe.smn = min_share_bps;
+        universe.smx = max_share_bps;
+
+        emit!(SharePolicyUpdated {
+            universe: universe.key(),
+            min_share_bps,
+            max_share_bps,
+        });
+        Ok(())
+    }
+
+
+    /// -------------------------------------------------------------------///
+    /// UPDATE STATS BUCKET
+    /// Deposited/withdrawn stats are the running totals rounded to this many lamports (0 = exact).
+    /// Reporting only: the exact ledger (td, tw) stays public for the invariant check,
+    /// so bucketing does not keep individual deposit sizes private.
+    ///-------------------------------------------------------------------///
+    pub fn update_stats_bucket(ctx: Context<UpdateUniverseStatsBucket>, bucket: u64) -> Result<()> {
+        let universe: &mut Account<Universe> = &mut ctx.accounts.universe;
+        universe.bk = bucket;
+        universe.sd = bucket_amount(universe.td, bucket)?;
+        universe.sw = bucket_amount(universe.tw, bucket)?;
+
+        emit!(StatsBucketUpdated {
+            universe: universe.key(),
+            bucket,
+        });
+        Ok(())
+    }
+
+    ///-------------------------------------------------------------------///
+    /// UPDATE PAUSE
+    /// A paused universe rejects new deposits and hops.
+    /// Withdrawals, star hop ends and star recovery still work so funds can leave.
+    ///-------------------------------------------------------------------///
+    pub fn update_pause(ctx: Context<UpdateUniversePause>, paused: bool) -> Result<()> {
+        let universe: &mut Account<Universe> = &mut ctx.accounts.universe;
+        universe.pa = paused;
+
+        emit!(UniversePauseUpdated {
+            universe: universe.key(),
+            paused,
+        });
+        Ok(())
+    }
+
+    ///-------------------------------------------------------------------///
+    /// UPDATE PLANET CAP
+    /// Max lamports (above rent) a single planet may hold. Deposits and hops that would
+    /// push a planet over the cap are rejected. 0 disables the cap.
+    ///-------------------------------------------------------------------///
+    pub fn update_planet_cap(ctx: Context<UpdateUniversePlanetCap>, cap: u64) -> Result<()> {
+        let universe: &mut Account<Universe> = &mut ctx.accounts.universe;
+        universe.mb = cap;
+
+        emit!(PlanetCapUpdated {
+            universe: universe.key(),
+            cap,
+        });
+        Ok(())
+    }
+
+    /// -------------------------------------------------------------------///
+    /// UPDATE DENOMINATIONS
+    /// Fixed deposit amounts (lamports, token units in token universes).
+    /// Deposits of the same size cannot be told apart by amount. Empty = any amount.
+    ///-------------------------------------------------------------------///
+    pub fn update_denominations(ctx: Context<UpdateUniverseDenominations>, denominations: Vec<u64>) -> Result<()> {
+        assert_valid_denominations(&denominations)?;
+        let universe: &mut Account<Universe> = &mut ctx.accounts.universe;
+        universe.dn = denominations.clone();
+
+        emit!(DenominationsUpdated {
+            universe: universe.key(),
+            denominations,
+        });
+        Ok(())
+    }
+
+    /// -------------------------------------------------------------------///
+    /// SWEEP TREASURY
+    /// Moves collected fees and staking rewards out of the universe.
+    /// At most fees + rewards - already swept, so deposits held by the universe cannot leave.
+    /// Planet and star balances are untouched, verify_invariants keeps balancing.
+    ///-------------------------------------------------------------------///
+    pub fn sweep_treasury(ctx: Context<SweepTreasury>, lamports: u64) -> Result<()> {
+        let universe: &mut Account<Universe> = &mut ctx.accounts.universe;
+        let sweepable: u64 = checked_sub(checked_sum(&[universe.tf, universe.rw])?, universe.ts)?;
+        if lamports == 0 || lamports > sweepable {
+            return Err(errors::ErrorCode::SweepExceedsTreasury.into())
+        }
+
+        // TRANSACTION - From galaxy to destination
+        assert_rent_floor(&universe.to_account_info(), lamports)?;
+        universe.sub_lamports(lamports)?;
+        ctx.accounts.destination.add_lamports(lamports)?;
+        universe.ts = checked_sum(&[universe.ts, lamports])?;
+
+        emit!(TreasurySwept {
+            universe: universe.key(),
+            destination: ctx.accounts.destination.key(),
+            amount: lamports,
+        });
+        Ok(())
+    }
+
+    ///-------------------------------------------------------------------///
+    /// ALLOWLIST
+    /// Destinations of any owner (PDAs, stake accounts, ...) that withdrawals may pay into.
+    ///-------------------------------------------------------------------///
+    pub fn create_allowlist(ctx: Context<CreateAllowlist>) -> Result<()> {
+        ctx.accounts.allowlist.universe = ctx.accounts.universe.key();
+        Ok(())
+    }
+
+    pub fn allow_destination(ctx: Context<UpdateAllowlist>, destination: Pubkey) -> Result<()> {
+        let allowlist: &mut Account<Allowlist> = &mut ctx.accounts.allowlist;
+        if allowlist.d.contains(&destination) {
+            return Err(errors::ErrorCode::AllowlistEntryExists.into())
+        }
+        if allowlist.d.len() >= MAX_ALLOWLIST_DESTINATIONS {
+            return Err(errors::ErrorCode::AllowlistFull.into())
+        }
+        allowlist.d.push(destination);
+
+        emit!(AllowlistUpdated {
+            universe: allowlist.universe,
+            destination,
+            allowed: true,
+        });
+        Ok(())
+    }
+
+    pub fn disallow_destination(ctx: Context<UpdateAllowlist>, destination: Pubkey) -> Result<()> {
+        let allowlist: &mut Account<Allowlist> = &mut ctx.accounts.allowlist;
+        if !allowlist.d.contains(&destination) {
+            return Err(errors::ErrorCode::DestinationNotAllowlisted.into())
+        }
+        allowlist.d.retain(|allowed| allowed != &destination);
+
+        emit!(AllowlistUpdated {
+            universe: allowlist.universe,
+            destination,
+            allowed: false,
+        });
+        Ok(())
+    }
+
+    pub fn allow_program(ctx: Context<UpdateAllowlist>, program: Pubkey) -> Result<()> {
+        let allowlist: &mut Account<Allowlist> = &mut ctx.accounts.allowlist;
+        if allowlist.pg.contains(&program) {
+            return Err(errors::ErrorCode::AllowlistEntryExists.into())
+        }
+        if allowlist.pg.len() >= MAX_ALLOWLIST_PROGRAMS {
+            return Err(errors::ErrorCode::AllowlistFull.into())
+        }
+        allowlist.pg.push(program);
+
+        emit!(ProgramAllowlistUpdated {
+            universe: allowlist.universe,
+            program,
+            allowed: true,
+        });
+        Ok(())
+    }
+
+    pub fn disallow_program(ctx: Context<UpdateAllowlist>, program: Pubkey) -> Result<()> {
+        let allowlist: &mut Account<Allowlist> = &mut ctx.accounts.allowlist;
+        if !allowlist.pg.contains(&program) {
+            return Err(errors::ErrorCode::ProgramNotAllowlisted.into())
+        }
+        allowlist.pg.retain(|allowed| allowed != &program);
+
+        emit!(ProgramAllowlistUpdated {
+            universe: allowlist.universe,
+            program,
+            allowed: false,
+        });
         Ok(())
     }
 
@@ -70,12 +339,17 @@ pub mod oridion {
         planet.name = name.clone();
         planet.created = clock.unix_timestamp;
         planet.bump = ctx.bumps.planet;
-        planet.pda = get_planet_program_address(&name,&ctx.program_id);
+        planet.pda = get_planet_program_address(&name, &universe.mt, ctx.program_id);
+        planet.universe = universe.key();
         planet.visits = 0;
         //Universe
         universe.p.push(name.clone());
-        //msg!("== PLANET {} CREATED! ==", name.to_string());
-        //msg!("== PLANET CREATED! ==");
+
+        emit!(PlanetCreated {
+            planet: ctx.accounts.planet.key(),
+            #[cfg(feature = "event-details")]
+            name,
+        });
         Ok(())
     }
 
@@ -85,58 +359,220 @@ pub mod oridion {
     pub fn delete_planet(ctx: Context<DeletePlanet>) -> Result<()> {
         let planet_lamports = ctx.accounts.planet.get_lamports();
 
-        //ERROR! PLANET HAS MONEY STILL IN IT! (1593840 == empty planet) 1593840
-        if planet_lamports > 1593840 {
-            //msg!("Error deleting planet | Lamports: {}", planet_lamports.to_string());
+        //ERROR! PLANET HAS MONEY STILL IN IT! (rent exempt minimum == empty planet)
+        if planet_lamports > rent_exempt_minimum(&ctx.accounts.planet.to_account_info())? {
             return Err(errors::ErrorCode::PlanetDeleteHasFundsError.into())
         }
+        let mint: Option<AccountInfo> = ctx.accounts.mint.as_ref().map(|mint| mint.to_account_info());
+        let vault: Option<AccountInfo> = ctx.accounts.vault.as_ref().map(|vault| vault.to_account_info());
+        assert_planet_vault_empty(&ctx.accounts.universe, &ctx.accounts.planet.to_account_info(), mint.as_ref(), vault.as_ref())?;
 
         //Remove planet from universe list
         let universe: &mut Account<Universe> = &mut ctx.accounts.universe;
         let planet: &mut Account<Planet> = &mut ctx.accounts.planet;
         universe.p.retain(|x| x != &planet.name);
-        //msg!("== PLANET {} DELETED ==", planet.name.to_string());
-        //msg!("== PLANET DELETED ==");
+
+        emit!(PlanetDeleted {
+            planet: ctx.accounts.planet.key(),
+            #[cfg(feature = "event-details")]
+            name: ctx.accounts.planet.name.clone(),
+        });
+        Ok(())
+    }
+
+    ///-------------------------------------------------------------------///
+    /// CLOSE PLANET
+    /// First step of retiring a planet. A closed planet no longer accepts
+    /// deposits or incoming hops, so in-flight comets can be routed elsewhere.
+    ///-------------------------------------------------------------------///
+    pub fn close_planet(ctx: Context<ClosePlanet>) -> Result<()> {
+        let planet: &mut Account<Planet> = &mut ctx.accounts.planet;
+        planet.closed = true;
+
+        emit!(PlanetClosed {
+            planet: planet.key(),
+        });
+        Ok(())
+    }
+
+    ///-------------------------------------------------------------------///
+    /// RETIRE PLANET
+    /// Sweeps the spendable balance of a closed planet into the target planets
+    /// passed through `remaining_accounts`, removes it from the universe and closes it.
+    /// - Balance is split equally, the rounding remainder goes to the first target
+    /// - Targets get a visit and count as a planet hop (same as rebalance)
+    /// - Token universes: the planet vault must be empty (moved out with token hops first)
+    ///-------------------------------------------------------------------///
+    pub fn retire_planet<'info>(ctx: Context<'_, '_, 'info, 'info, RetirePlanet<'info>>) -> Result<()> {
+        let retiring_key: Pubkey = ctx.accounts.planet.key();
+        let targets: &[AccountInfo<'info>] = ctx.remaining_accounts;
+        if targets.is_empty() {
+            return Err(errors::ErrorCode::PlanetRetireInvalidTarget.into())
+        }
+        let mint: Option<AccountInfo> = ctx.accounts.mint.as_ref().map(|mint| mint.to_account_info());
+        let vault: Option<AccountInfo> = ctx.accounts.vault.as_ref().map(|vault| vault.to_account_info());
+        assert_planet_vault_empty(&ctx.accounts.universe, &ctx.accounts.planet.to_account_info(), mint.as_ref(), vault.as_ref())?;
+
+        let planet_info: AccountInfo = ctx.accounts.planet.to_account_info();
+        let spendable: u64 = checked_sub(planet_info.lamports(), rent_exempt_minimum(&planet_info)?)?;
+        let share: u64 = spendable / targets.len() as u64;
+        let remainder: u64 = spendable % targets.len() as u64;
+
+        // TRANSACTIONS - From retiring planet to each target planet
+        let mut to_planets: Vec<Pubkey> = Vec::new();
+        for (index, target_info) in targets.iter().enumerate() {
+            if !target_info.is_writable || *target_info.key == retiring_key || to_planets.contains(target_info.key) {
+                return Err(errors::ErrorCode::PlanetRetireInvalidTarget.into())
+            }
+            let mut target: Account<Planet> = Account::try_from(target_info)?;
+            if target.closed || target.pda != *target_info.key || target.universe != ctx.accounts.universe.key() || !ctx.accounts.universe.p.contains(&target.name) {
+                return Err(errors::ErrorCode::PlanetRetireInvalidTarget.into())
+            }
+
+            let amount: u64 = if index == 0 { checked_sum(&[share, remainder])? } else { share };
+            add_visit(&mut target)?;
+            target.exit(ctx.program_id)?;
+            target.add_lamports(amount)?;
+            assert_planet_cap(target_info, &ctx.accounts.universe)?;
+            record_planet_hop(&mut ctx.accounts.universe)?;
+            to_planets.push(*target_info.key);
+        }
+        assert_rent_floor(&planet_info, spendable)?;
+        ctx.accounts.planet.sub_lamports(spendable)?;
+
+        //Remove planet from universe list
+        let name: String = ctx.accounts.planet.name.clone();
+        ctx.accounts.universe.p.retain(|x| x != &name);
+
+        emit!(PlanetRetired {
+            planet: retiring_key,
+            to_planets,
+            #[cfg(feature = "event-details")]
+            name,
+            #[cfg(feature = "event-details")]
+            amount: spendable,
+        });
+        Ok(())
+    }
+
+    ///-------------------------------------------------------------------///
+    /// REBALANCE
+    /// Moves lamports between the planets passed through `remaining_accounts`
+    /// toward a target distribution in a single transaction.
+    /// - Empty weights = equal distribution, otherwise one weight (basis points) per planet
+    /// - The rounding remainder goes to the first planet
+    /// - Planets receiving lamports get a visit and count as a planet hop
+    ///-------------------------------------------------------------------///
+    pub fn rebalance<'info>(ctx: Context<'_, '_, 'info, 'info, Rebalance<'info>>, weights: Vec<u16>) -> Result<()> {
+        let planet_infos: &[AccountInfo<'info>] = ctx.remaining_accounts;
+        if planet_infos.len() < 2 {
+            return Err(errors::ErrorCode::RebalanceInvalidPlanet.into())
+        }
+        if !weights.is_empty() && (weights.len() != planet_infos.len()
+            || weights.iter().map(|weight| *weight as u32).sum::<u32>() != BPS_TOTAL as u32) {
+            return Err(errors::ErrorCode::RebalanceInvalidWeights.into())
+        }
+
+        let mut planets: Vec<Account<Planet>> = Vec::new();
+        let mut balances: Vec<u64> = Vec::new();
+        for planet_info in planet_infos.iter() {
+            if !planet_info.is_writable || planets.iter().any(|planet| planet.key() == *planet_info.key) {
+                return Err(errors::ErrorCode::RebalanceInvalidPlanet.into())
+            }
+            let planet: Account<Planet> = Account::try_from(planet_info)?;
+            if planet.closed || planet.pda != *planet_info.key || planet.universe != ctx.accounts.universe.key() || !ctx.accounts.universe.p.contains(&planet.name) {
+                return Err(errors::ErrorCode::RebalanceInvalidPlanet.into())
+            }
+            balances.push(spendable_lamports(planet_info)?);
+            planets.push(planet);
+        }
+
+        //Target balances
+        let total: u64 = checked_sum(&balances)?;
+        let mut targets: Vec<u64> = if weights.is_empty() {
+            vec![total / planets.len() as u64; planets.len()]
+        } else {
+            weights
+                .iter()
+                .map(|weight| mul_div(total, *weight as u64, BPS_TOTAL as u64))
+                .collect::<Result<Vec<u64>>>()?
+        };
+        targets[0] = checked_sum(&[targets[0], checked_sub(total, checked_sum(&targets)?)?])?;
+
+        // TRANSACTIONS - From planets over their target
+        let mut moved: u64 = 0;
+        for (index, planet) in planets.iter_mut().enumerate() {
+            if balances[index] > targets[index] {
+                let amount: u64 = checked_sub(balances[index], targets[index])?;
+                assert_rent_floor(&planet.to_account_info(), amount)?;
+                planet.sub_lamports(amount)?;
+                moved = checked_sum(&[moved, amount])?;
+            }
+        }
+
+        // TRANSACTIONS - To planets under their target
+        for (index, planet) in planets.iter_mut().enumerate() {
+            if balances[index] < targets[index] {
+                add_visit(planet)?;
+                planet.exit(ctx.program_id)?;
+                planet.add_lamports(checked_sub(targets[index], balances[index])?)?;
+                assert_planet_cap(&planet.to_account_info(), &ctx.accounts.universe)?;
+                record_planet_hop(&mut ctx.accounts.universe)?;
+            }
+        }
+
+        emit!(PlanetsRebalanced {
+            planets: planets.iter().map(|planet| planet.key()).collect(),
+            #[cfg(feature = "event-details")]
+            amount: moved,
+        });
         Ok(())
     }
 
     ///-------------------------------------------------------------------///
     /// CREATE DEPOSIT
     /// Creates user's deposit and handles transfer from galaxy to planet. 
-    /// - Signed by user
+    /// - Signed by user, or by a partner program PDA through CPI (see `CreateComet`)
     /// - Deposit (In lamports)
     /// - Occurs before creating deposit account.
     ///-------------------------------------------------------------------///
     pub fn new_comet(ctx: Context<CreateComet>,deposit_lamports: u64) -> Result<()> {
+        let fee: u64 = ctx.accounts.universe.cfe;
+        deposit_comet(ctx.accounts, deposit_lamports, fee)?;
 
-        //msg!("Deposit: {} Lamports", deposit_lamports);
+        emit!(CometCreated {
+            planet: ctx.accounts.planet.key(),
+            #[cfg(feature = "event-details")]
+            amount: deposit_lamports,
+        });
+        Ok(())
+    }
 
-        // INCREMENT VISIT
-        let planet: &mut Account<Planet> = &mut ctx.accounts.planet;
-        planet.visits += 1;
-    
-        // TRANSACTION SETUP
-        let creator_account: &Signer = &ctx.accounts.creator;
-        let universe_account: &Account<Universe> = &ctx.accounts.universe;
-        //let planet_account: &Account<Planet> = &ctx.accounts.planet;
-
-        // DEPOSIT TRANSFER
-        let transfer_instruction: Instruction = system_instruction::transfer(creator_account.key, universe_account.to_account_info().key, deposit_lamports);
-        solana_program::program::invoke_signed(
-            &transfer_instruction,
-            &[
-                creator_account.to_account_info(),
-                universe_account.to_account_info(),
-                ctx.accounts.system_program.to_account_info(),
-            ],
-            &[],
-        )?;
+    /// CREATE DEPOSIT WITH A HOP PACKAGE
+    /// Accounts of new_comet plus the comet. The comet fee and the hop fees of the package
+    /// are kept from the deposit, and the hop credits are stored on the comet.
+    /// Every hop start consumes one credit of its kind from the comet.
+    pub fn new_comet_with_hops(ctx: Context<CreateCometWithHops>, deposit_lamports: u64, hops: HopPackage) -> Result<()> {
+        let fee: u64 = comet_fee(&ctx.accounts.deposit.universe, &hops)?;
+        deposit_comet(&mut ctx.accounts.deposit, deposit_lamports, fee)?;
+        let universe: Pubkey = ctx.accounts.deposit.universe.key();
+        let creator: Pubkey = ctx.accounts.deposit.creator.key();
+        open_comet(&mut ctx.accounts.comet, universe, creator, &hops, ctx.bumps.comet);
+
+        emit!(CometCreated {
+            planet: ctx.accounts.deposit.planet.key(),
+            #[cfg(feature = "event-details")]
+            amount: deposit_lamports,
+        });
+        Ok(())
+    }
 
-        // TRANSACTION - From galaxy to planet
-        //msg!("Hopping from galaxy to {}", planet_account.name);
-        ctx.accounts.planet.add_lamports(deposit_lamports)?;
-        ctx.accounts.universe.sub_lamports(deposit_lamports)?;
-        //msg!("Deposit and initial hop completed");
+    /// CLOSE COMET
+    /// Returns the comet rent to the creator. Remaining hop credits are forfeited.
+    pub fn close_comet(ctx: Context<CloseComet>) -> Result<()> {
+        emit!(CometClosed {
+            comet: ctx.accounts.comet.key(),
+        });
         Ok(())
     }
 
@@ -146,6 +582,7 @@ pub mod oridion {
     /// The "to planet" validation is not needed because the user signs the transaction
     /// -------------------------------------------------------------------///
     pub fn planet_hop(ctx: Context<PlanetHop>,lamports: u64) -> Result<()>{
+        consume_hop_credit(&mut ctx.accounts.comet.ph)?;
         let from: &mut Account<Planet> = &mut ctx.accounts.from_planet;
         let to: &mut Account<Planet> = &mut ctx.accounts.to_planet;
 
@@ -155,31 +592,126 @@ pub mod oridion {
         }
 
         //Increment visits
-        to.visits += 1;
+        add_visit(to)?;
 
         // TRANSACTION: Move funds from planet to planet
         ctx.accounts.to_planet.add_lamports(lamports)?;
+        assert_planet_cap(&ctx.accounts.to_planet.to_account_info(), &ctx.accounts.universe)?;
+        assert_rent_floor(&ctx.accounts.from_planet.to_account_info(), lamports)?;
         ctx.accounts.from_planet.sub_lamports(lamports)?;
-        //msg!("Planet hop completed");
+
+        //Universe stats
+        record_planet_hop(&mut ctx.accounts.universe)?;
+
+        emit!(PlanetHopped {
+            from_planet: ctx.accounts.from_planet.key(),
+            to_planet: ctx.accounts.to_planet.key(),
+            #[cfg(feature = "event-details")]
+            amount: lamports,
+        });
         Ok(())
     }
 
     /// WITHDRAW COMET FUNDS TO FINAL DESTINATION. 
     /// - THIS ONLY HANDLES THE TRANSACTION FROM PLANET TO FINAL USER WALLET. 
     /// - This is just like planet hop except deliver to destination wallet
+    /// - The withdraw fee is kept from withdraw_lamports (the destination receives withdraw_lamports - fee)
     pub fn withdraw(ctx: Context<WithdrawAccounts>, withdraw_lamports: u64) -> Result<()> {
-        let from: &mut Account<Planet> = &mut ctx.accounts.from_planet;
+        let destination: AccountInfo = ctx.accounts.destination.to_account_info();
+        #[cfg_attr(not(feature = "event-details"), allow(unused_variables))]
+        let payout: u64 = withdraw_comet(&mut ctx.accounts.universe, &mut ctx.accounts.from_planet, &destination, ctx.accounts.oracle.as_ref(), withdraw_lamports)?;
 
-        //VALIDATION 
-        let current_from_lamports_balance: u64 = from.get_lamports();
-        if current_from_lamports_balance <= withdraw_lamports {
-            return Err(errors::ErrorCode::PlanetNotEnoughFundsError.into())
+        emit!(Withdrawn {
+            from_planet: ctx.accounts.from_planet.key(),
+            #[cfg(feature = "event-details")]
+            destination: ctx.accounts.destination.key(),
+            #[cfg(feature = "event-details")]
+            amount: payout,
+        });
+        Ok(())
+    }
+
+    /// WITHDRAW TO A NEW DESTINATION
+    /// - Destination may not exist yet (zero lamports)
+    /// - When the payout does not cover the rent exempt minimum the manager tops it up
+    pub fn withdraw_to_new(ctx: Context<WithdrawToNewAccounts>, withdraw_lamports: u64) -> Result<()> {
+        let oracle: Option<AccountInfo> = ctx.accounts.oracle.as_ref().map(|oracle| oracle.to_account_info());
+        let fee: u64 = fee_lamports(ctx.accounts.universe.wfe, &ctx.accounts.universe, oracle.as_ref())?;
+        let payout: u64 = withdraw_payout(withdraw_lamports, fee)?;
+
+        // RENT TOP UP - From manager to destination (before the payout moves lamports directly)
+        let destination: AccountInfo = ctx.accounts.destination.to_account_info();
+        let paid_out: u64 = checked_sum(&[destination.lamports(), payout])?;
+        let shortfall: u64 = rent_exempt_minimum(&destination)?.saturating_sub(paid_out);
+        if shortfall > 0 {
+            let transfer_accounts = system_program::Transfer {
+                from: ctx.accounts.manager.to_account_info(),
+                to: destination.clone(),
+            };
+            system_program::transfer(CpiContext::new(ctx.accounts.system_program.to_account_info(), transfer_accounts), shortfall)?;
         }
 
-        // TRANSACTION - Transfer to destination
-        ctx.accounts.destination.add_lamports(withdraw_lamports)?;
-        ctx.accounts.from_planet.sub_lamports(withdraw_lamports)?;
-        //msg!("Withdraw completed");
+        withdraw_comet(&mut ctx.accounts.universe, &mut ctx.accounts.from_planet, &destination, ctx.accounts.oracle.as_ref(), withdraw_lamports)?;
+
+        emit!(Withdrawn {
+            from_planet: ctx.accounts.from_planet.key(),
+            #[cfg(feature = "event-details")]
+            destination: ctx.accounts.destination.key(),
+            #[cfg(feature = "event-details")]
+            amount: payout,
+        });
+        Ok(())
+    }
+
+    /// WITHDRAW TO AN ALLOWLISTED DESTINATION
+    /// - Destination can be owned by any program (PDA, stake account, ...)
+    /// - Destination must be in the universe allowlist
+    pub fn withdraw_to_allowlisted(ctx: Context<WithdrawToAllowlistedAccounts>, withdraw_lamports: u64) -> Result<()> {
+        let destination: AccountInfo = ctx.accounts.destination.to_account_info();
+        #[cfg_attr(not(feature = "event-details"), allow(unused_variables))]
+        let payout: u64 = withdraw_comet(&mut ctx.accounts.universe, &mut ctx.accounts.from_planet, &destination, ctx.accounts.oracle.as_ref(), withdraw_lamports)?;
+
+        emit!(Withdrawn {
+            from_planet: ctx.accounts.from_planet.key(),
+            #[cfg(feature = "event-details")]
+            destination: ctx.accounts.destination.key(),
+            #[cfg(feature = "event-details")]
+            amount: payout,
+        });
+        Ok(())
+    }
+
+    /// WITHDRAW AND INVOKE
+    /// - Pays out to the destination, then calls an allowlisted program with the given data
+    /// - The call is made without program signatures, and remaining accounts cannot be signers
+    /// - The destination must be one of the remaining accounts, the planet and universe are
+    ///   appended read-only (every account the withdrawal changed is part of the call)
+    /// - Payout and call are atomic (a failing call reverts the withdrawal)
+    pub fn withdraw_and_invoke<'info>(ctx: Context<'_, '_, 'info, 'info, WithdrawAndInvokeAccounts<'info>>, withdraw_lamports: u64, data: Vec<u8>) -> Result<()> {
+        let changed: [AccountInfo<'info>; 2] = [ctx.accounts.from_planet.to_account_info(), ctx.accounts.universe.to_account_info()];
+        let (metas, mut infos) = invoke_accounts(ctx.remaining_accounts, ctx.accounts.destination.key, &changed)?;
+
+        let destination: AccountInfo<'info> = ctx.accounts.destination.to_account_info();
+        #[cfg_attr(not(feature = "event-details"), allow(unused_variables))]
+        let payout: u64 = withdraw_comet(&mut ctx.accounts.universe, &mut ctx.accounts.from_planet, &destination, ctx.accounts.oracle.as_ref(), withdraw_lamports)?;
+
+        // CALLBACK - Target program with remaining accounts (plus planet and universe)
+        infos.push(ctx.accounts.target_program.to_account_info());
+        let instruction = Instruction {
+            program_id: ctx.accounts.target_program.key(),
+            accounts: metas,
+            data,
+        };
+        invoke(&instruction, &infos)?;
+
+        emit!(WithdrawnAndInvoked {
+            from_planet: ctx.accounts.from_planet.key(),
+            program: ctx.accounts.target_program.key(),
+            #[cfg(feature = "event-details")]
+            destination: ctx.accounts.destination.key(),
+            #[cfg(feature = "event-details")]
+            amount: payout,
+        });
         Ok(())
     }
 
@@ -187,39 +719,36 @@ pub mod oridion {
 
     ///-------------------------------------------------------------------///
     /// STAR HOP SECTION
+    /// Star addresses are handed out by the universe star counter.
+    /// Star one uses the current counter and star two the next one.
     ///-------------------------------------------------------------------///
-     pub fn star_hop_two_start(ctx: Context<StarHopTwoStart>, star_one :String , star_two: String, deposit: u64) -> Result<()>{
-        //let clock: Clock = Clock::get().unwrap();
+     pub fn star_hop_two_start(ctx: Context<StarHopTwoStart>, deposit: u64) -> Result<()>{
+        consume_hop_credit(&mut ctx.accounts.comet.s2)?;
+        let clock: Clock = Clock::get().unwrap();
+        let origin: Pubkey = ctx.accounts.from_planet.key();
+        let star_id: u64 = ctx.accounts.universe.sc;
         //let from: &mut Account<Planet> = &mut ctx.accounts.from_planet;
         let manager: &Signer = &ctx.accounts.manager;
         let star1: &mut Account<Star> = &mut ctx.accounts.star_one;
         let star2: &mut Account<Star> = &mut ctx.accounts.star_two;
         //let from_planet_name: String = from.name.to_owned();
 
-        // IMPORTANT VALIDATION: STAR ONE AND TWO CANNOT BE THE SAME
-        if star_one == star_two {
-            return Err(errors::ErrorCode::HopErrorStarsMustBeUnique.into())
-        }
-
-        //msg!("Validation successful");
-
         // GET DEPOSIT SPLIT AMOUNT
-        let percent: f32 = get_random_percent();
-        let star_one_amount: u64 = ((percent / 100f32) * deposit as f32) as u64;
-        let star_two_amount: u64 = deposit - star_one_amount;
-        //msg!("Hopping to star 1: {}", star_one_amount.to_string());
-        //msg!("Hopping to Star 2: {}", star_two_amount.to_string());
-
-        //Make sure the addition of split amounts are equal to deposit
-        if star_one_amount + star_two_amount != deposit {
-            return Err(errors::ErrorCode::StarHopCalculationError.into())
-        }
+        let (star_one_amount, star_two_amount) = split_deposit_two(deposit, get_random_percent()?)?;
 
         //Set amounts to accounts
-        star1.amount = star_one_amount.clone();
-        star2.amount = star_two_amount.clone();
+        star1.id = star_id;
+        star2.id = star_id_offset(star_id, 1)?;
+        star1.amount = star_one_amount;
+        star2.amount = star_two_amount;
         star1.manager = *manager.key;
         star2.manager = *manager.key;
+        star1.created = clock.unix_timestamp;
+        star2.created = clock.unix_timestamp;
+        star1.origin = origin;
+        star2.origin = origin;
+        star1.in_use = true;
+        star2.in_use = true;
 
         //----------------------------------------///
         // TRANSACTION
@@ -227,8 +756,22 @@ pub mod oridion {
         //----------------------------------------///
         ctx.accounts.star_one.add_lamports(star_one_amount)?;
         ctx.accounts.star_two.add_lamports(star_two_amount)?;
+        assert_rent_floor(&ctx.accounts.from_planet.to_account_info(), deposit)?;
         ctx.accounts.from_planet.sub_lamports(deposit)?;
 
+        //Move the star counter past the used ids
+        ctx.accounts.universe.sc = star_id_offset(star_id, 2)?;
+
+        //Universe ledger
+        open_stars(&mut ctx.accounts.universe, 2)?;
+        record_star_hop(&mut ctx.accounts.universe, 2)?;
+
+        emit!(StarHopStarted {
+            from_planet: ctx.accounts.from_planet.key(),
+            stars: vec![ctx.accounts.star_one.key(), ctx.accounts.star_two.key()],
+            #[cfg(feature = "event-details")]
+            amounts: vec![star_one_amount, star_two_amount],
+        });
         Ok(())
     }
 
@@ -242,10 +785,10 @@ pub mod oridion {
         //let to_planet_name: String = to.name.to_owned();
         let star1: &mut Account<Star> = &mut ctx.accounts.star_one;
         let star2: &mut Account<Star> = &mut ctx.accounts.star_two;
-        let star_one_amount: u64 = star1.amount.clone();
-        let star_two_amount: u64 = star2.amount.clone();
+        let star_one_amount: u64 = star1.amount;
+        let star_two_amount: u64 = star2.amount;
 
-        if star_one_amount + star_two_amount != deposit {
+        if checked_sum(&[star_one_amount, star_two_amount])? != deposit {
             return Err(errors::ErrorCode::StarHopCalculationError.into())
         }
 
@@ -254,19 +797,19 @@ pub mod oridion {
         star2.amount = 0;
      
         //Increment planet visit
-        to.visits += 1;
+        add_visit(to)?;
         
         // TRANSACTIONS
         // Transaction from stars one and two to destination planet       
-        let total_lamports: u64 = star_one_amount + star_two_amount;
+        let total_lamports: u64 = checked_sum(&[star_one_amount, star_two_amount])?;
+        assert_rent_floor(&ctx.accounts.star_one.to_account_info(), star_one_amount)?;
         ctx.accounts.star_one.sub_lamports(star_one_amount)?;
+        assert_rent_floor(&ctx.accounts.star_two.to_account_info(), star_two_amount)?;
         ctx.accounts.star_two.sub_lamports(star_two_amount)?;
         ctx.accounts.to_planet.add_lamports(total_lamports)?;
-        //msg!("Hop from two stars to {} complete", to_planet_name);     
-        //msg!("Hop from two stars to planet complete");     
+        assert_planet_cap(&ctx.accounts.to_planet.to_account_info(), &ctx.accounts.universe)?;
 
         // EXPLODE STARS
-        //msg!("Exploding stars..");
         //Transfer out remaining lamports
         let star_one_remaining_lamports = ctx.accounts.star_one.get_lamports();
         let star_two_remaining_lamports = ctx.accounts.star_two.get_lamports();
@@ -274,7 +817,16 @@ pub mod oridion {
         ctx.accounts.star_one.sub_lamports(star_one_remaining_lamports)?;
         ctx.accounts.manager.add_lamports(star_two_remaining_lamports)?;
         ctx.accounts.star_two.sub_lamports(star_two_remaining_lamports)?;
-        //msg!("Star hop 2 completed!");
+
+        //Universe ledger
+        close_stars(&mut ctx.accounts.universe, 2)?;
+
+        emit!(StarHopEnded {
+            to_planets: vec![ctx.accounts.to_planet.key()],
+            stars: vec![ctx.accounts.star_one.key(), ctx.accounts.star_two.key()],
+            #[cfg(feature = "event-details")]
+            amount: deposit,
+        });
         Ok(())
     }
 
@@ -283,8 +835,12 @@ pub mod oridion {
 
     
     /// STAR HOP THREE
-    pub fn star_hop_three_start(ctx: Context<StarHopThreeStart>, star_one :String , star_two: String, star_three: String, deposit: u64) -> Result<()>{
-        //let clock: Clock = Clock::get().unwrap();
+    /// Stars use the current universe star counter and the two following ids.
+    pub fn star_hop_three_start(ctx: Context<StarHopThreeStart>, deposit: u64) -> Result<()>{
+        consume_hop_credit(&mut ctx.accounts.comet.s3)?;
+        let clock: Clock = Clock::get().unwrap();
+        let origin: Pubkey = ctx.accounts.from_planet.key();
+        let star_id: u64 = ctx.accounts.universe.sc;
         //let from: &mut Account<Planet> = &mut ctx.accounts.from_planet;
         //let from_planet_name: String = from.name.to_owned();
         let manager: &Signer = &ctx.accounts.manager;
@@ -293,62 +849,137 @@ pub mod oridion {
         let star2: &mut Account<Star> = &mut ctx.accounts.star_two;
         let star3: &mut Account<Star> = &mut ctx.accounts.star_three;
 
-        // IMPORTANT VALIDATION: STAR ONE AND TWO CANNOT BE THE SAME
-        if star_one == star_two || star_two == star_three || star_one == star_three {
-            return Err(errors::ErrorCode::HopErrorStarsMustBeUnique.into())
-        }
-
-        //msg!("Validation successful");
-
         // GET DEPOSIT SPLIT AMOUNT
-        let first_split_percent: f32 = get_random_percent();
-        let second_split_percent: f32 = 100f32 - first_split_percent;
-        //msg!("Split: {}% / {}%", first_split_percent.to_string(), second_split_percent.to_string());
-        
-        //Determine side amounts here
-        let side_one_amount: u64 = ((first_split_percent / 100f32) * deposit as f32) as u64;
-        let side_two_amount: u64 = deposit - side_one_amount;
-
-        let (star_one_amount, star_two_amount, star_three_amount) = if side_one_amount > side_two_amount {
-            //Side one is larger so we split side one.
-            //Side two is set as star 2
-            //We further split side one to create star one and star three
-            let one: u64 = ((second_split_percent / 100f32) * side_one_amount as f32) as u64;
-            let three: u64 = side_one_amount - one;
-            //msg!("Third split on first side");
-            (one,side_two_amount,three)
-        } else {
-            //Side two is larger so we split side two.
-            //Side one is set as star one
-            //We further split side two to create side two and side three
-            let two: u64 = ((second_split_percent / 100f32) * side_two_amount as f32) as u64;
-            let three: u64 = side_two_amount - two;
-            //msg!("Third split on second side");
-            (side_one_amount,two,three)
-        };
-
-        if star_one_amount + star_two_amount + star_three_amount != deposit {
-            return Err(errors::ErrorCode::StarHopCalculationError.into())
-        }
+        let (star_one_amount, star_two_amount, star_three_amount) = split_deposit_three(deposit, get_random_percent()?)?;
 
-        //msg!("Hopping to star 1: {}", star_one_amount.to_string());
-        //msg!("Hopping to star 2: {}", star_two_amount.to_string());
-        //msg!("Hopping to star 3: {}", star_three_amount.to_string());
 
+        star1.id = star_id;
+        star2.id = star_id_offset(star_id, 1)?;
+        star3.id = star_id_offset(star_id, 2)?;
         star1.amount = star_one_amount;
         star2.amount = star_two_amount;
         star3.amount = star_three_amount;
         star1.manager = *manager.key;
         star2.manager = *manager.key;
         star3.manager = *manager.key;
+        star1.created = clock.unix_timestamp;
+        star2.created = clock.unix_timestamp;
+        star3.created = clock.unix_timestamp;
+        star1.origin = origin;
+        star2.origin = origin;
+        star3.origin = origin;
+        star1.in_use = true;
+        star2.in_use = true;
+        star3.in_use = true;
 
         // Transfer from planet to star one and two
         ctx.accounts.star_one.add_lamports(star_one_amount)?;
         ctx.accounts.star_two.add_lamports(star_two_amount)?;
         ctx.accounts.star_three.add_lamports(star_three_amount)?;
+        assert_rent_floor(&ctx.accounts.from_planet.to_account_info(), deposit)?;
+        ctx.accounts.from_planet.sub_lamports(deposit)?;
+
+        //Move the star counter past the used ids
+        ctx.accounts.universe.sc = star_id_offset(star_id, 3)?;
+
+        //Universe ledger
+        open_stars(&mut ctx.accounts.universe, 3)?;
+        record_star_hop(&mut ctx.accounts.universe, 3)?;
+
+        emit!(StarHopStarted {
+            from_planet: ctx.accounts.from_planet.key(),
+            stars: vec![ctx.accounts.star_one.key(), ctx.accounts.star_two.key(), ctx.accounts.star_three.key()],
+            #[cfg(feature = "event-details")]
+            amounts: vec![star_one_amount, star_two_amount, star_three_amount],
+        });
+        Ok(())
+    }
+
+
+    ///-------------------------------------------------------------------///
+    /// STAR HOP TWO START WEIGHTED
+    /// Same as star hop two start but the manager dictates the star shares
+    /// (e.g. to match a denomination set) instead of the clock derived percent.
+    /// - weights: one share per star in basis points, must add up to 10000
+    /// - every share must respect the universe min/max share policy
+    ///-------------------------------------------------------------------///
+    pub fn star_hop_two_start_weighted(ctx: Context<StarHopTwoStart>, deposit: u64, weights: Vec<u16>) -> Result<()>{
+        consume_hop_credit(&mut ctx.accounts.comet.s2)?;
+        let clock: Clock = Clock::get().unwrap();
+        let origin: Pubkey = ctx.accounts.from_planet.key();
+        let manager: Pubkey = ctx.accounts.manager.key();
+        let star_id: u64 = ctx.accounts.universe.sc;
+
+        // GET DEPOSIT SPLIT AMOUNT
+        let amounts: Vec<u64> = split_deposit_weighted(deposit, &weights, 2, &ctx.accounts.universe)?;
+
+        light_star(&mut ctx.accounts.star_one, star_id, amounts[0], manager, origin, clock.unix_timestamp);
+        light_star(&mut ctx.accounts.star_two, star_id_offset(star_id, 1)?, amounts[1], manager, origin, clock.unix_timestamp);
+
+        // TRANSACTION
+        // Transfer from planet to star one and two
+        ctx.accounts.star_one.add_lamports(amounts[0])?;
+        ctx.accounts.star_two.add_lamports(amounts[1])?;
+        assert_rent_floor(&ctx.accounts.from_planet.to_account_info(), deposit)?;
+        ctx.accounts.from_planet.sub_lamports(deposit)?;
+
+        //Move the star counter past the used ids
+        ctx.accounts.universe.sc = star_id_offset(star_id, 2)?;
+
+        //Universe ledger
+        open_stars(&mut ctx.accounts.universe, 2)?;
+        record_star_hop(&mut ctx.accounts.universe, 2)?;
+
+        emit!(StarHopStarted {
+            from_planet: ctx.accounts.from_planet.key(),
+            stars: vec![ctx.accounts.star_one.key(), ctx.accounts.star_two.key()],
+            #[cfg(feature = "event-details")]
+            amounts: vec![amounts[0], amounts[1]],
+        });
+        Ok(())
+    }
+
+
+    ///-------------------------------------------------------------------///
+    /// STAR HOP THREE START WEIGHTED
+    /// Same as star hop three start with manager supplied star shares.
+    /// See star hop two start weighted.
+    ///-------------------------------------------------------------------///
+    pub fn star_hop_three_start_weighted(ctx: Context<StarHopThreeStart>, deposit: u64, weights: Vec<u16>) -> Result<()>{
+        consume_hop_credit(&mut ctx.accounts.comet.s3)?;
+        let clock: Clock = Clock::get().unwrap();
+        let origin: Pubkey = ctx.accounts.from_planet.key();
+        let manager: Pubkey = ctx.accounts.manager.key();
+        let star_id: u64 = ctx.accounts.universe.sc;
+
+        // GET DEPOSIT SPLIT AMOUNT
+        let amounts: Vec<u64> = split_deposit_weighted(deposit, &weights, 3, &ctx.accounts.universe)?;
+
+        light_star(&mut ctx.accounts.star_one, star_id, amounts[0], manager, origin, clock.unix_timestamp);
+        light_star(&mut ctx.accounts.star_two, star_id_offset(star_id, 1)?, amounts[1], manager, origin, clock.unix_timestamp);
+        light_star(&mut ctx.accounts.star_three, star_id_offset(star_id, 2)?, amounts[2], manager, origin, clock.unix_timestamp);
+
+        // TRANSACTION
+        // Transfer from planet to stars
+        ctx.accounts.star_one.add_lamports(amounts[0])?;
+        ctx.accounts.star_two.add_lamports(amounts[1])?;
+        ctx.accounts.star_three.add_lamports(amounts[2])?;
+        assert_rent_floor(&ctx.accounts.from_planet.to_account_info(), deposit)?;
         ctx.accounts.from_planet.sub_lamports(deposit)?;
-        //msg!("Hop from {} to three stars complete", from_planet_name);
-        //msg!("Hop to three stars complete");
+
+        //Move the star counter past the used ids
+        ctx.accounts.universe.sc = star_id_offset(star_id, 3)?;
+
+        //Universe ledger
+        open_stars(&mut ctx.accounts.universe, 3)?;
+        record_star_hop(&mut ctx.accounts.universe, 3)?;
+
+        emit!(StarHopStarted {
+            from_planet: ctx.accounts.from_planet.key(),
+            stars: vec![ctx.accounts.star_one.key(), ctx.accounts.star_two.key(), ctx.accounts.star_three.key()],
+            #[cfg(feature = "event-details")]
+            amounts: vec![amounts[0], amounts[1], amounts[2]],
+        });
         Ok(())
     }
 
@@ -359,11 +990,11 @@ pub mod oridion {
         let star1: &mut Account<Star> = &mut ctx.accounts.star_one;
         let star2: &mut Account<Star> = &mut ctx.accounts.star_two;
         let star3: &mut Account<Star> = &mut ctx.accounts.star_three;
-        let star_one_amount: u64 = star1.amount.clone();
-        let star_two_amount: u64 = star2.amount.clone();
-        let star_three_amount: u64 = star3.amount.clone();
+        let star_one_amount: u64 = star1.amount;
+        let star_two_amount: u64 = star2.amount;
+        let star_three_amount: u64 = star3.amount;
 
-        if star_one_amount + star_two_amount + star_three_amount != deposit {
+        if checked_sum(&[star_one_amount, star_two_amount, star_three_amount])? != deposit {
             return Err(errors::ErrorCode::StarHopCalculationError.into())
         }
 
@@ -373,24 +1004,22 @@ pub mod oridion {
         star3.amount = 0;
      
         //Increment planet visit
-        to.visits += 1;
+        add_visit(to)?;
     
-        //msg!("Star 1: {}", star_one_amount.to_string());
-        //msg!("Star 2: {}", star_two_amount.to_string());
-        //msg!("Star 3: {}", star_three_amount.to_string());
 
         // TRANSACTIONS
         // Transaction from stars one and two to destination planet
+        assert_rent_floor(&ctx.accounts.star_one.to_account_info(), star_one_amount)?;
         ctx.accounts.star_one.sub_lamports(star_one_amount)?;
+        assert_rent_floor(&ctx.accounts.star_two.to_account_info(), star_two_amount)?;
         ctx.accounts.star_two.sub_lamports(star_two_amount)?;
+        assert_rent_floor(&ctx.accounts.star_three.to_account_info(), star_three_amount)?;
         ctx.accounts.star_three.sub_lamports(star_three_amount)?;
         ctx.accounts.to_planet.add_lamports(deposit)?;
-        //msg!("Hop to {} complete", to_planet_name);
-        //msg!("Hop to planet complete");
+        assert_planet_cap(&ctx.accounts.to_planet.to_account_info(), &ctx.accounts.universe)?;
 
 
         // EXPLODE STARS
-        //msg!("Exploding stars..");
         //Transfer out remaining lamports
         let star_one_remaining_lamports = ctx.accounts.star_one.get_lamports();
         let star_two_remaining_lamports = ctx.accounts.star_two.get_lamports();
@@ -403,8 +1032,1353 @@ pub mod oridion {
 
         ctx.accounts.manager.add_lamports(star_three_remaining_lamports)?;
         ctx.accounts.star_three.sub_lamports(star_three_remaining_lamports)?;
-        //msg!("Star Hop 3 completed");
+
+        //Universe ledger
+        close_stars(&mut ctx.accounts.universe, 3)?;
+
+        emit!(StarHopEnded {
+            to_planets: vec![ctx.accounts.to_planet.key()],
+            stars: vec![ctx.accounts.star_one.key(), ctx.accounts.star_two.key(), ctx.accounts.star_three.key()],
+            #[cfg(feature = "event-details")]
+            amount: deposit,
+        });
         Ok(())
     }
 
+
+    ///-------------------------------------------------------------------///
+    /// STAR HOP TWO END SPLIT
+    /// Fan-in variant of star hop two end. Each star lands on its own destination planet
+    /// so a single hop also redistributes liquidity across the galaxy.
+    /// - Star one lands on planet one
+    /// - Star two lands on planet two
+    ///-------------------------------------------------------------------///
+    pub fn star_hop_two_end_split(ctx: Context<StarHopTwoEndSplit>, deposit: u64) -> Result<()>{
+        let star_one_amount: u64 = ctx.accounts.star_one.amount;
+        let star_two_amount: u64 = ctx.accounts.star_two.amount;
+
+        // IMPORTANT VALIDATION: DESTINATION PLANETS CANNOT BE THE SAME
+        if ctx.accounts.to_planet_one.key() == ctx.accounts.to_planet_two.key() {
+            return Err(errors::ErrorCode::HopErrorPlanetsMustBeUnique.into())
+        }
+
+        if checked_sum(&[star_one_amount, star_two_amount])? != deposit {
+            return Err(errors::ErrorCode::StarHopCalculationError.into())
+        }
+
+        //Clear our star amount
+        ctx.accounts.star_one.amount = 0;
+        ctx.accounts.star_two.amount = 0;
+
+        //Increment planet visits
+        add_visit(&mut ctx.accounts.to_planet_one)?;
+        add_visit(&mut ctx.accounts.to_planet_two)?;
+
+        // TRANSACTIONS
+        // Each star lands on its own destination planet
+        assert_rent_floor(&ctx.accounts.star_one.to_account_info(), star_one_amount)?;
+        ctx.accounts.star_one.sub_lamports(star_one_amount)?;
+        ctx.accounts.to_planet_one.add_lamports(star_one_amount)?;
+        assert_planet_cap(&ctx.accounts.to_planet_one.to_account_info(), &ctx.accounts.universe)?;
+        assert_rent_floor(&ctx.accounts.star_two.to_account_info(), star_two_amount)?;
+        ctx.accounts.star_two.sub_lamports(star_two_amount)?;
+        ctx.accounts.to_planet_two.add_lamports(star_two_amount)?;
+        assert_planet_cap(&ctx.accounts.to_planet_two.to_account_info(), &ctx.accounts.universe)?;
+
+        // EXPLODE STARS
+        //Transfer out remaining lamports
+        let star_one_remaining_lamports = ctx.accounts.star_one.get_lamports();
+        let star_two_remaining_lamports = ctx.accounts.star_two.get_lamports();
+        ctx.accounts.manager.add_lamports(star_one_remaining_lamports)?;
+        ctx.accounts.star_one.sub_lamports(star_one_remaining_lamports)?;
+        ctx.accounts.manager.add_lamports(star_two_remaining_lamports)?;
+        ctx.accounts.star_two.sub_lamports(star_two_remaining_lamports)?;
+
+        //Universe ledger
+        close_stars(&mut ctx.accounts.universe, 2)?;
+
+        emit!(StarHopEnded {
+            to_planets: vec![ctx.accounts.to_planet_one.key(), ctx.accounts.to_planet_two.key()],
+            stars: vec![ctx.accounts.star_one.key(), ctx.accounts.star_two.key()],
+            #[cfg(feature = "event-details")]
+            amount: deposit,
+        });
+        Ok(())
+    }
+
+
+    ///-------------------------------------------------------------------///
+    /// STAR HOP THREE END SPLIT
+    /// Fan-in variant of star hop three end.
+    /// `landing` maps each star (one, two, three) to a destination planet index (0, 1, 2).
+    /// - Planet three is optional so stars can be partitioned between two planets.
+    /// - Every supplied planet must receive at least one star.
+    ///-------------------------------------------------------------------///
+    pub fn star_hop_three_end_split(ctx: Context<StarHopThreeEndSplit>, deposit: u64, landing: [u8; 3]) -> Result<()>{
+        let star_amounts: [u64; 3] = [
+            ctx.accounts.star_one.amount,
+            ctx.accounts.star_two.amount,
+            ctx.accounts.star_three.amount,
+        ];
+
+        if checked_sum(&star_amounts)? != deposit {
+            return Err(errors::ErrorCode::StarHopCalculationError.into())
+        }
+
+        // IMPORTANT VALIDATION: DESTINATION PLANETS CANNOT BE THE SAME
+        let planet_one_key: Pubkey = ctx.accounts.to_planet_one.key();
+        let planet_two_key: Pubkey = ctx.accounts.to_planet_two.key();
+        let planet_three_key: Option<Pubkey> = ctx.accounts.to_planet_three.as_ref().map(|p| p.key());
+        if planet_one_key == planet_two_key || planet_three_key == Some(planet_one_key) || planet_three_key == Some(planet_two_key) {
+            return Err(errors::ErrorCode::HopErrorPlanetsMustBeUnique.into())
+        }
+
+        //Sum what lands on each planet
+        let planet_count: usize = if planet_three_key.is_some() { 3 } else { 2 };
+        let mut landed: [u64; 3] = [0, 0, 0];
+        for (star_amount, planet_index) in star_amounts.iter().zip(landing.iter()) {
+            let planet_index: usize = *planet_index as usize;
+            if planet_index >= planet_count {
+                return Err(errors::ErrorCode::HopErrorInvalidLanding.into())
+            }
+            landed[planet_index] = checked_sum(&[landed[planet_index], *star_amount])?;
+        }
+
+        //Every supplied planet must receive a star (an empty landing would be a free visit)
+        if landed.iter().take(planet_count).any(|amount| *amount == 0) {
+            return Err(errors::ErrorCode::HopErrorInvalidLanding.into())
+        }
+
+        //Make sure nothing was lost while partitioning
+        if checked_sum(&landed)? != deposit {
+            return Err(errors::ErrorCode::StarHopCalculationError.into())
+        }
+
+        //Clear our star amount
+        ctx.accounts.star_one.amount = 0;
+        ctx.accounts.star_two.amount = 0;
+        ctx.accounts.star_three.amount = 0;
+
+        // TRANSACTIONS
+        // Transaction from stars to destination planets
+        assert_rent_floor(&ctx.accounts.star_one.to_account_info(), star_amounts[0])?;
+        ctx.accounts.star_one.sub_lamports(star_amounts[0])?;
+        assert_rent_floor(&ctx.accounts.star_two.to_account_info(), star_amounts[1])?;
+        ctx.accounts.star_two.sub_lamports(star_amounts[1])?;
+        assert_rent_floor(&ctx.accounts.star_three.to_account_info(), star_amounts[2])?;
+        ctx.accounts.star_three.sub_lamports(star_amounts[2])?;
+
+        add_visit(&mut ctx.accounts.to_planet_one)?;
+        ctx.accounts.to_planet_one.add_lamports(landed[0])?;
+        assert_planet_cap(&ctx.accounts.to_planet_one.to_account_info(), &ctx.accounts.universe)?;
+        add_visit(&mut ctx.accounts.to_planet_two)?;
+        ctx.accounts.to_planet_two.add_lamports(landed[1])?;
+        assert_planet_cap(&ctx.accounts.to_planet_two.to_account_info(), &ctx.accounts.universe)?;
+        let mut to_planets: Vec<Pubkey> = vec![planet_one_key, planet_two_key];
+        to_planets.extend(planet_three_key);
+        if let Some(to_planet_three) = ctx.accounts.to_planet_three.as_mut() {
+            add_visit(to_planet_three)?;
+            to_planet_three.add_lamports(landed[2])?;
+            assert_planet_cap(&to_planet_three.to_account_info(), &ctx.accounts.universe)?;
+        }
+
+        // EXPLODE STARS
+        //Transfer out remaining lamports
+        let star_one_remaining_lamports = ctx.accounts.star_one.get_lamports();
+        let star_two_remaining_lamports = ctx.accounts.star_two.get_lamports();
+        let star_three_remaining_lamports = ctx.accounts.star_three.get_lamports();
+        ctx.accounts.manager.add_lamports(star_one_remaining_lamports)?;
+        ctx.accounts.star_one.sub_lamports(star_one_remaining_lamports)?;
+
+        ctx.accounts.manager.add_lamports(star_two_remaining_lamports)?;
+        ctx.accounts.star_two.sub_lamports(star_two_remaining_lamports)?;
+
+        ctx.accounts.manager.add_lamports(star_three_remaining_lamports)?;
+        ctx.accounts.star_three.sub_lamports(star_three_remaining_lamports)?;
+
+        //Universe ledger
+        close_stars(&mut ctx.accounts.universe, 3)?;
+
+        emit!(StarHopEnded {
+            to_planets,
+            stars: vec![ctx.accounts.star_one.key(), ctx.accounts.star_two.key(), ctx.accounts.star_three.key()],
+            #[cfg(feature = "event-details")]
+            amount: deposit,
+        });
+        Ok(())
+    }
+
+
+    ///-------------------------------------------------------------------///
+    /// RECOVER STAR
+    /// Returns the funds of a stranded star after the universe star timeout and explodes the star.
+    /// Pool stars are deactivated instead of exploded.
+    /// Used when the operator crashes between a star hop start and end.
+    /// - Funds return to the planet the star was split from
+    /// - Or to any registered fallback planet (origin planet may have been deleted)
+    ///-------------------------------------------------------------------///
+    pub fn recover_star(ctx: Context<RecoverStar>) -> Result<()>{
+        let star: &Account<Star> = &ctx.accounts.star;
+        assert_star_recoverable(&ctx.accounts.universe, star, &ctx.accounts.to_planet)?;
+
+        let star_amount: u64 = star.amount;
+        let star_key: Pubkey = star.key();
+        ctx.accounts.star.amount = 0;
+        ctx.accounts.star.in_use = false;
+        add_visit(&mut ctx.accounts.to_planet)?;
+
+        // TRANSACTION - From star back to planet
+        assert_rent_floor(&ctx.accounts.star.to_account_info(), star_amount)?;
+        ctx.accounts.star.sub_lamports(star_amount)?;
+        ctx.accounts.to_planet.add_lamports(star_amount)?;
+        assert_planet_cap(&ctx.accounts.to_planet.to_account_info(), &ctx.accounts.universe)?;
+
+        // Pool stars are deactivated and stay in the pool, other stars explode
+        if !ctx.accounts.star.pooled {
+            ctx.accounts.star.close(ctx.accounts.manager.to_account_info())?;
+        }
+
+        //Universe ledger
+        close_stars(&mut ctx.accounts.universe, 1)?;
+
+        emit!(StarRecovered {
+            star: star_key,
+            to_planet: ctx.accounts.to_planet.key(),
+            #[cfg(feature = "event-details")]
+            amount: star_amount,
+        });
+        Ok(())
+    }
+
+    /// RECOVER TOKEN STAR
+    /// Same as recover star for token stars: the whole star vault returns to the
+    /// origin (or a registered fallback) planet vault, then the vault and star are closed.
+    pub fn recover_token_star(ctx: Context<RecoverTokenStar>) -> Result<()> {
+        assert_star_recoverable(&ctx.accounts.universe, &ctx.accounts.star, &ctx.accounts.to_planet)?;
+
+        ctx.accounts.star.amount = 0;
+        ctx.accounts.star.in_use = false;
+        add_visit(&mut ctx.accounts.to_planet)?;
+
+        // TRANSACTION - Star vault back to planet vault (signed by the star)
+        let star_seed: [u8; 32] = star_seed(ctx.accounts.star.id, &ctx.accounts.star.mint);
+        let star_bump: [u8; 1] = [ctx.accounts.star.bump];
+        let star_signer: &[&[&[u8]]] = &[&[STAR_SEED_PRE, &star_seed, STAR_SEED_POST, &star_bump]];
+        let star: AccountInfo = ctx.accounts.star.to_account_info();
+        let held: u64 = ctx.accounts.star_vault.amount;
+        let landed: u64 = transfer_tokens(&ctx.accounts.token_program, &ctx.accounts.star_vault, &mut ctx.accounts.to_vault, star.clone(), &ctx.accounts.mint, held, star_signer)?;
+        close_star_vault(&ctx.accounts.token_program, &ctx.accounts.mint, &ctx.accounts.star_vault, star, ctx.accounts.manager.to_account_info(), star_signer)?;
+
+        //Universe ledger
+        record_transfer_fee(&mut ctx.accounts.universe, held, landed)?;
+        close_stars(&mut ctx.accounts.universe, 1)?;
+
+        emit!(StarRecovered {
+            star: ctx.accounts.star.key(),
+            to_planet: ctx.accounts.to_planet.key(),
+            #[cfg(feature = "event-details")]
+            amount: landed,
+        });
+        Ok(())
+    }
+
+
+    ///-------------------------------------------------------------------///
+    /// STAR POOL SECTION
+    /// Pool stars are long lived star accounts. Hops activate them (amount set)
+    /// and deactivate them (amount cleared) instead of creating and exploding stars,
+    /// saving two account creations and rent round-trips per hop.
+    ///
+    /// GROW STAR POOL
+    /// Adds one star to the end of the pool. Rent is paid by the manager.
+    ///-------------------------------------------------------------------///
+    pub fn grow_star_pool(ctx: Context<GrowStarPool>) -> Result<()>{
+        let universe: &mut Account<Universe> = &mut ctx.accounts.universe;
+        let star: &mut Account<Star> = &mut ctx.accounts.star;
+        star.id = universe.sp as u64; //Pool stars use their pool index
+        star.amount = 0;
+        star.manager = ctx.accounts.manager.key();
+        star.pooled = true;
+        star.in_use = false;
+        universe.sp = universe.sp.checked_add(1).ok_or(errors::ErrorCode::MathOverflow)?;
+
+        emit!(StarPoolResized {
+            size: ctx.accounts.universe.sp,
+        });
+        Ok(())
+    }
+
+    /// SHRINK STAR POOL
+    /// Explodes the last star of the pool and returns its rent to the manager.
+    pub fn shrink_star_pool(ctx: Context<ShrinkStarPool>) -> Result<()>{
+        let universe: &mut Account<Universe> = &mut ctx.accounts.universe;
+        if universe.sp == 0 {
+            return Err(errors::ErrorCode::StarPoolEmpty.into())
+        }
+        universe.sp = universe.sp.checked_sub(1).ok_or(errors::ErrorCode::MathOverflow)?;
+
+        emit!(StarPoolResized {
+            size: ctx.accounts.universe.sp,
+        });
+        Ok(())
+    }
+
+    /// STAR POOL HOP TWO START
+    /// Same as star hop two start using two idle pool stars.
+    pub fn star_pool_hop_two_start(ctx: Context<StarPoolHopTwoStart>, deposit: u64) -> Result<()>{
+        consume_hop_credit(&mut ctx.accounts.comet.s2)?;
+        let clock: Clock = Clock::get().unwrap();
+        let origin: Pubkey = ctx.accounts.from_planet.key();
+
+        // IMPORTANT VALIDATION: STAR ONE AND TWO CANNOT BE THE SAME
+        if ctx.accounts.star_one.key() == ctx.accounts.star_two.key() {
+            return Err(errors::ErrorCode::HopErrorStarsMustBeUnique.into())
+        }
+
+        // GET DEPOSIT SPLIT AMOUNT
+        let (star_one_amount, star_two_amount) = split_deposit_two(deposit, get_random_percent()?)?;
+
+        // ACTIVATE STARS
+        let star1: &mut Account<Star> = &mut ctx.accounts.star_one;
+        star1.amount = star_one_amount;
+        star1.created = clock.unix_timestamp;
+        star1.origin = origin;
+        star1.in_use = true;
+        let star2: &mut Account<Star> = &mut ctx.accounts.star_two;
+        star2.amount = star_two_amount;
+        star2.created = clock.unix_timestamp;
+        star2.origin = origin;
+        star2.in_use = true;
+
+        // TRANSACTION
+        // Transfer from planet to star one and two
+        ctx.accounts.star_one.add_lamports(star_one_amount)?;
+        ctx.accounts.star_two.add_lamports(star_two_amount)?;
+        assert_rent_floor(&ctx.accounts.from_planet.to_account_info(), deposit)?;
+        ctx.accounts.from_planet.sub_lamports(deposit)?;
+
+        //Universe ledger
+        open_stars(&mut ctx.accounts.universe, 2)?;
+        record_star_hop(&mut ctx.accounts.universe, 2)?;
+
+        emit!(StarHopStarted {
+            from_planet: ctx.accounts.from_planet.key(),
+            stars: vec![ctx.accounts.star_one.key(), ctx.accounts.star_two.key()],
+            #[cfg(feature = "event-details")]
+            amounts: vec![star_one_amount, star_two_amount],
+        });
+        Ok(())
+    }
+
+    /// STAR POOL HOP TWO END
+    /// Same as star hop two end. Stars are deactivated and returned to the pool.
+    pub fn star_pool_hop_two_end(ctx: Context<StarPoolHopTwoEnd>, deposit: u64) -> Result<()>{
+        let star_one_amount: u64 = ctx.accounts.star_one.amount;
+        let star_two_amount: u64 = ctx.accounts.star_two.amount;
+
+        if checked_sum(&[star_one_amount, star_two_amount])? != deposit {
+            return Err(errors::ErrorCode::StarHopCalculationError.into())
+        }
+
+        // DEACTIVATE STARS
+        ctx.accounts.star_one.amount = 0;
+        ctx.accounts.star_one.in_use = false;
+        ctx.accounts.star_two.amount = 0;
+        ctx.accounts.star_two.in_use = false;
+
+        //Increment planet visit
+        add_visit(&mut ctx.accounts.to_planet)?;
+
+        // TRANSACTIONS
+        // Transaction from stars one and two to destination planet
+        assert_rent_floor(&ctx.accounts.star_one.to_account_info(), star_one_amount)?;
+        ctx.accounts.star_one.sub_lamports(star_one_amount)?;
+        assert_rent_floor(&ctx.accounts.star_two.to_account_info(), star_two_amount)?;
+        ctx.accounts.star_two.sub_lamports(star_two_amount)?;
+        ctx.accounts.to_planet.add_lamports(deposit)?;
+        assert_planet_cap(&ctx.accounts.to_planet.to_account_info(), &ctx.accounts.universe)?;
+
+        //Universe ledger
+        close_stars(&mut ctx.accounts.universe, 2)?;
+
+        emit!(StarHopEnded {
+            to_planets: vec![ctx.accounts.to_planet.key()],
+            stars: vec![ctx.accounts.star_one.key(), ctx.accounts.star_two.key()],
+            #[cfg(feature = "event-details")]
+            amount: deposit,
+        });
+        Ok(())
+    }
+
+    /// STAR POOL HOP THREE START
+    /// Same as star hop three start using three idle pool stars.
+    pub fn star_pool_hop_three_start(ctx: Context<StarPoolHopThreeStart>, deposit: u64) -> Result<()>{
+        consume_hop_credit(&mut ctx.accounts.comet.s3)?;
+        let clock: Clock = Clock::get().unwrap();
+        let origin: Pubkey = ctx.accounts.from_planet.key();
+
+        // IMPORTANT VALIDATION: STARS CANNOT BE THE SAME
+        let star_one_key: Pubkey = ctx.accounts.star_one.key();
+        let star_two_key: Pubkey = ctx.accounts.star_two.key();
+        let star_three_key: Pubkey = ctx.accounts.star_three.key();
+        if star_one_key == star_two_key || star_two_key == star_three_key || star_one_key == star_three_key {
+            return Err(errors::ErrorCode::HopErrorStarsMustBeUnique.into())
+        }
+
+        // GET DEPOSIT SPLIT AMOUNT
+        let (star_one_amount, star_two_amount, star_three_amount) = split_deposit_three(deposit, get_random_percent()?)?;
+
+        // ACTIVATE STARS
+        let star1: &mut Account<Star> = &mut ctx.accounts.star_one;
+        star1.amount = star_one_amount;
+        star1.created = clock.unix_timestamp;
+        star1.origin = origin;
+        star1.in_use = true;
+        let star2: &mut Account<Star> = &mut ctx.accounts.star_two;
+        star2.amount = star_two_amount;
+        star2.created = clock.unix_timestamp;
+        star2.origin = origin;
+        star2.in_use = true;
+        let star3: &mut Account<Star> = &mut ctx.accounts.star_three;
+        star3.amount = star_three_amount;
+        star3.created = clock.unix_timestamp;
+        star3.origin = origin;
+        star3.in_use = true;
+
+        // TRANSACTION
+        // Transfer from planet to stars
+        ctx.accounts.star_one.add_lamports(star_one_amount)?;
+        ctx.accounts.star_two.add_lamports(star_two_amount)?;
+        ctx.accounts.star_three.add_lamports(star_three_amount)?;
+        assert_rent_floor(&ctx.accounts.from_planet.to_account_info(), deposit)?;
+        ctx.accounts.from_planet.sub_lamports(deposit)?;
+
+        //Universe ledger
+        open_stars(&mut ctx.accounts.universe, 3)?;
+        record_star_hop(&mut ctx.accounts.universe, 3)?;
+
+        emit!(StarHopStarted {
+            from_planet: ctx.accounts.from_planet.key(),
+            stars: vec![ctx.accounts.star_one.key(), ctx.accounts.star_two.key(), ctx.accounts.star_three.key()],
+            #[cfg(feature = "event-details")]
+            amounts: vec![star_one_amount, star_two_amount, star_three_amount],
+        });
+        Ok(())
+    }
+
+    /// STAR POOL HOP THREE END
+    /// Same as star hop three end. Stars are deactivated and returned to the pool.
+    pub fn star_pool_hop_three_end(ctx: Context<StarPoolHopThreeEnd>, deposit: u64) -> Result<()>{
+        let star_one_amount: u64 = ctx.accounts.star_one.amount;
+        let star_two_amount: u64 = ctx.accounts.star_two.amount;
+        let star_three_amount: u64 = ctx.accounts.star_three.amount;
+
+        if checked_sum(&[star_one_amount, star_two_amount, star_three_amount])? != deposit {
+            return Err(errors::ErrorCode::StarHopCalculationError.into())
+        }
+
+        // DEACTIVATE STARS
+        ctx.accounts.star_one.amount = 0;
+        ctx.accounts.star_one.in_use = false;
+        ctx.accounts.star_two.amount = 0;
+        ctx.accounts.star_two.in_use = false;
+        ctx.accounts.star_three.amount = 0;
+        ctx.accounts.star_three.in_use = false;
+
+        //Increment planet visit
+        add_visit(&mut ctx.accounts.to_planet)?;
+
+        // TRANSACTIONS
+        // Transaction from stars to destination planet
+        assert_rent_floor(&ctx.accounts.star_one.to_account_info(), star_one_amount)?;
+        ctx.accounts.star_one.sub_lamports(star_one_amount)?;
+        assert_rent_floor(&ctx.accounts.star_two.to_account_info(), star_two_amount)?;
+        ctx.accounts.star_two.sub_lamports(star_two_amount)?;
+        assert_rent_floor(&ctx.accounts.star_three.to_account_info(), star_three_amount)?;
+        ctx.accounts.star_three.sub_lamports(star_three_amount)?;
+        ctx.accounts.to_planet.add_lamports(deposit)?;
+        assert_planet_cap(&ctx.accounts.to_planet.to_account_info(), &ctx.accounts.universe)?;
+
+        //Universe ledger
+        close_stars(&mut ctx.accounts.universe, 3)?;
+
+        emit!(StarHopEnded {
+            to_planets: vec![ctx.accounts.to_planet.key()],
+            stars: vec![ctx.accounts.star_one.key(), ctx.accounts.star_two.key(), ctx.accounts.star_three.key()],
+            #[cfg(feature = "event-details")]
+            amount: deposit,
+        });
+        Ok(())
+    }
+
+
+    ///-------------------------------------------------------------------///
+    /// STAKING SECTION
+    /// Idle planet lamports can be delegated to the planet stake account (see accounts_stake).
+    /// Staked principal stays in the ledger (universe.sk), rewards go to the universe.
+    /// Flow: stake_planet -> deactivate_planet_stake -> (cooldown) -> withdraw_planet_stake
+    ///-------------------------------------------------------------------///
+    pub fn update_stake_fraction(ctx: Context<UpdateUniverseStakeFraction>, fraction: u16) -> Result<()> {
+        if fraction > BPS_TOTAL {
+            return Err(errors::ErrorCode::InvalidStakeFraction.into())
+        }
+        let universe: &mut Account<Universe> = &mut ctx.accounts.universe;
+        universe.sf = fraction;
+
+        emit!(StakeFractionUpdated {
+            universe: universe.key(),
+            fraction,
+        });
+        Ok(())
+    }
+
+    /// STAKE PLANET
+    /// - lamports: at most the universe stake fraction of the planet spendable lamports
+    pub fn stake_planet(ctx: Context<StakePlanet>, lamports: u64) -> Result<()> {
+        //VALIDATION
+        let universe: &Account<Universe> = &ctx.accounts.universe;
+        if universe.sf == 0 {
+            return Err(errors::ErrorCode::StakingDisabled.into())
+        }
+        let stake_info: AccountInfo = ctx.accounts.stake_account.to_account_info();
+        if ctx.accounts.planet.sk != 0 || *stake_info.owner != system_program::ID || !stake_info.data_is_empty() {
+            return Err(errors::ErrorCode::PlanetAlreadyStaked.into())
+        }
+        let planet_info: AccountInfo = ctx.accounts.planet.to_account_info();
+        let limit: u64 = mul_div(spendable_lamports(&planet_info)?, universe.sf as u64, BPS_TOTAL as u64)?;
+        if lamports == 0 || lamports > limit {
+            return Err(errors::ErrorCode::StakeAmountInvalid.into())
+        }
+
+        // CREATE - Stake account (rent paid by manager)
+        // Transfer + allocate + assign instead of create_account: anyone can send lamports to the
+        // stake address beforehand, which would make create_account fail.
+        let planet_key: Pubkey = ctx.accounts.planet.key();
+        let stake_bump: [u8; 1] = [ctx.bumps.stake_account];
+        let stake_seeds: &[&[&[u8]]] = &[&[STAKE_SEED, planet_key.as_ref(), &stake_bump]];
+        let stake_rent: u64 = ctx.accounts.rent.minimum_balance(StakeStateV2::size_of());
+        let rent_shortfall: u64 = stake_rent.saturating_sub(stake_info.lamports());
+        if rent_shortfall > 0 {
+            let transfer_accounts = system_program::Transfer {
+                from: ctx.accounts.manager.to_account_info(),
+                to: stake_info.clone(),
+            };
+            system_program::transfer(CpiContext::new(ctx.accounts.system_program.to_account_info(), transfer_accounts), rent_shortfall)?;
+        }
+        let allocate_accounts = system_program::Allocate { account_to_allocate: stake_info.clone() };
+        system_program::allocate(
+            CpiContext::new_with_signer(ctx.accounts.system_program.to_account_info(), allocate_accounts, stake_seeds),
+            StakeStateV2::size_of() as u64,
+        )?;
+        let assign_accounts = system_program::Assign { account_to_assign: stake_info };
+        system_program::assign(
+            CpiContext::new_with_signer(ctx.accounts.system_program.to_account_info(), assign_accounts, stake_seeds),
+            &stake::program::ID,
+        )?;
+
+        // TRANSACTION - Principal from planet to stake account
+        ctx.accounts.stake_account.add_lamports(lamports)?;
+        assert_rent_floor(&planet_info, lamports)?;
+        ctx.accounts.planet.sub_lamports(lamports)?;
+
+        // DELEGATE - Universe is staker and withdrawer
+        let universe_key: Pubkey = universe.key();
+        let universe_bump: [u8; 1] = [universe.bp];
+        let universe_seeds: &[&[&[u8]]] = &[&[UNIVERSE_PDA_SEED, universe.mt.as_ref(), &universe_bump]];
+        let authorized = Authorized { staker: universe_key, withdrawer: universe_key };
+        invoke(
+            &stake::instruction::initialize(ctx.accounts.stake_account.key, &authorized, &Lockup::default()),
+            &[
+                ctx.accounts.stake_account.to_account_info(),
+                ctx.accounts.rent.to_account_info(),
+                ctx.accounts.stake_program.to_account_info(),
+            ],
+        )?;
+        invoke_signed(
+            &stake::instruction::delegate_stake(ctx.accounts.stake_account.key, &universe_key, ctx.accounts.vote_account.key),
+            &[
+                ctx.accounts.stake_account.to_account_info(),
+                ctx.accounts.vote_account.to_account_info(),
+                ctx.accounts.clock.to_account_info(),
+                ctx.accounts.stake_history.to_account_info(),
+                ctx.accounts.stake_config.to_account_info(),
+                ctx.accounts.universe.to_account_info(),
+                ctx.accounts.stake_program.to_account_info(),
+            ],
+            universe_seeds,
+        )?;
+
+        //Ledger
+        ctx.accounts.planet.sk = lamports;
+        let universe: &mut Account<Universe> = &mut ctx.accounts.universe;
+        universe.sk = checked_sum(&[universe.sk, lamports])?;
+
+        emit!(PlanetStaked {
+            planet: planet_key,
+            #[cfg(feature = "event-details")]
+            amount: lamports,
+        });
+        Ok(())
+    }
+
+    /// DEACTIVATE PLANET STAKE
+    /// Lamports can be withdrawn once the stake is inactive (after the cooldown epoch).
+    pub fn deactivate_planet_stake(ctx: Context<DeactivatePlanetStake>) -> Result<()> {
+        if ctx.accounts.planet.sk == 0 {
+            return Err(errors::ErrorCode::PlanetNotStaked.into())
+        }
+
+        let universe: &Account<Universe> = &ctx.accounts.universe;
+        let universe_bump: [u8; 1] = [universe.bp];
+        invoke_signed(
+            &stake::instruction::deactivate_stake(ctx.accounts.stake_account.key, &universe.key()),
+            &[
+                ctx.accounts.stake_account.to_account_info(),
+                ctx.accounts.clock.to_account_info(),
+                universe.to_account_info(),
+                ctx.accounts.stake_program.to_account_info(),
+            ],
+            &[&[UNIVERSE_PDA_SEED, universe.mt.as_ref(), &universe_bump]],
+        )?;
+
+        emit!(PlanetStakeDeactivated {
+            planet: ctx.accounts.planet.key(),
+        });
+        Ok(())
+    }
+
+    /// WITHDRAW PLANET STAKE
+    /// - Principal back to the planet (whatever is left of it when the stake came back short)
+    /// - Rewards to the universe (treasury)
+    /// - Stake account rent back to the manager (closes the stake account)
+    /// A missing principal is recorded in the ledger (universe.sl) so the stake can always be closed.
+    pub fn withdraw_planet_stake(ctx: Context<WithdrawPlanetStake>) -> Result<()> {
+        let principal: u64 = ctx.accounts.planet.sk;
+        if principal == 0 {
+            return Err(errors::ErrorCode::PlanetNotStaked.into())
+        }
+        let stake_rent: u64 = Rent::get()?.minimum_balance(StakeStateV2::size_of());
+        let (returned, rewards, shortfall, refund) = split_stake_withdraw(ctx.accounts.stake_account.lamports(), stake_rent, principal)?;
+
+        let universe_key: Pubkey = ctx.accounts.universe.key();
+        let universe_bump: [u8; 1] = [ctx.accounts.universe.bp];
+        let universe_mint: Pubkey = ctx.accounts.universe.mt;
+        let universe_seeds: &[&[&[u8]]] = &[&[UNIVERSE_PDA_SEED, universe_mint.as_ref(), &universe_bump]];
+        let payouts: [(AccountInfo, u64); 3] = [
+            (ctx.accounts.planet.to_account_info(), returned),
+            (ctx.accounts.universe.to_account_info(), rewards),
+            (ctx.accounts.manager.to_account_info(), refund),
+        ];
+        for (to, amount) in payouts.iter() {
+            if *amount == 0 {
+                continue
+            }
+            invoke_signed(
+                &stake::instruction::withdraw(ctx.accounts.stake_account.key, &universe_key, to.key, *amount, None),
+                &[
+                    ctx.accounts.stake_account.to_account_info(),
+                    to.clone(),
+                    ctx.accounts.clock.to_account_info(),
+                    ctx.accounts.stake_history.to_account_info(),
+                    ctx.accounts.universe.to_account_info(),
+                    ctx.accounts.stake_program.to_account_info(),
+                ],
+                universe_seeds,
+            )?;
+        }
+
+        //Ledger
+        ctx.accounts.planet.sk = 0;
+        let universe: &mut Account<Universe> = &mut ctx.accounts.universe;
+        universe.sk = checked_sub(universe.sk, principal)?;
+        universe.rw = checked_sum(&[universe.rw, rewards])?;
+        universe.sl = checked_sum(&[universe.sl, shortfall])?;
+
+        emit!(PlanetStakeWithdrawn {
+            planet: ctx.accounts.planet.key(),
+            #[cfg(feature = "event-details")]
+            amount: returned,
+            #[cfg(feature = "event-details")]
+            rewards,
+            #[cfg(feature = "event-details")]
+            shortfall,
+        });
+        Ok(())
+    }
+
+
+    ///-------------------------------------------------------------------///
+    /// TOKEN MODE SECTION
+    /// Same flows as the lamport instructions, moving SPL / Token-2022 tokens between the
+    /// planet and star vaults (associated token accounts owned by the PDAs).
+    /// Amounts are read back from the receiving vault so transfer fees are accounted for.
+    /// The universe ledger and stats count token units: fees (token units) go to the universe
+    /// treasury vault, transfer fees withheld between vaults are recorded (tl) and token stars
+    /// count as open stars. The planet cap does not apply.
+    ///-------------------------------------------------------------------///
+    pub fn create_planet_vault(ctx: Context<CreatePlanetVault>) -> Result<()> {
+        assert_supported_mint(&ctx.accounts.mint)
+    }
+
+    /// The comet fee (token units) goes to the universe treasury vault, the rest to the planet vault.
+    pub fn new_token_comet(ctx: Context<CreateTokenComet>, deposit: u64) -> Result<()> {
+        let fee: u64 = ctx.accounts.universe.cfe;
+        #[cfg_attr(not(feature = "event-details"), allow(unused_variables))]
+        let received: u64 = deposit_token_comet(ctx.accounts, deposit, fee)?;
+
+        emit!(CometCreated {
+            planet: ctx.accounts.planet.key(),
+            #[cfg(feature = "event-details")]
+            amount: received,
+        });
+        Ok(())
+    }
+
+    /// Token deposit with a hop package: accounts of new_token_comet plus the comet, the comet fee
+    /// and the hop fees of the package (token units) go to the universe treasury vault.
+    /// The hop credits are stored on the comet (see new_comet_with_hops).
+    pub fn new_token_comet_with_hops(ctx: Context<CreateTokenCometWithHops>, deposit: u64, hops: HopPackage) -> Result<()> {
+        let fee: u64 = comet_fee(&ctx.accounts.deposit.universe, &hops)?;
+        #[cfg_attr(not(feature = "event-details"), allow(unused_variables))]
+        let received: u64 = deposit_token_comet(&mut ctx.accounts.deposit, deposit, fee)?;
+        let universe: Pubkey = ctx.accounts.deposit.universe.key();
+        let creator: Pubkey = ctx.accounts.payer.key();
+        open_comet(&mut ctx.accounts.comet, universe, creator, &hops, ctx.bumps.comet);
+
+        emit!(CometCreated {
+            planet: ctx.accounts.deposit.planet.key(),
+            #[cfg(feature = "event-details")]
+            amount: received,
+        });
+        Ok(())
+    }
+
+    /// Creates the universe treasury vault (token fees), owned by the universe PDA.
+    pub fn create_treasury_vault(ctx: Context<CreateTreasuryVault>) -> Result<()> {
+        assert_supported_mint(&ctx.accounts.mint)
+    }
+
+    /// Token version of sweep_treasury: moves collected token fees out of the treasury vault.
+    /// At most fees - already swept (token units).
+    pub fn sweep_token_treasury(ctx: Context<SweepTokenTreasury>, amount: u64) -> Result<()> {
+        let sweepable: u64 = checked_sub(ctx.accounts.universe.tf, ctx.accounts.universe.ts)?;
+        if amount == 0 || amount > sweepable {
+            return Err(errors::ErrorCode::SweepExceedsTreasury.into())
+        }
+
+        // TRANSACTION - From treasury vault to destination (signed by the universe)
+        let mint: Pubkey = ctx.accounts.universe.mt;
+        let bump: [u8; 1] = [ctx.accounts.universe.bp];
+        transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.treasury_vault,
+            &mut ctx.accounts.destination,
+            ctx.accounts.universe.to_account_info(),
+            &ctx.accounts.mint,
+            amount,
+            &[&[UNIVERSE_PDA_SEED, mint.as_ref(), &bump]],
+        )?;
+        ctx.accounts.universe.ts = checked_sum(&[ctx.accounts.universe.ts, amount])?;
+
+        emit!(TreasurySwept {
+            universe: ctx.accounts.universe.key(),
+            destination: ctx.accounts.destination.key(),
+            amount,
+        });
+        Ok(())
+    }
+
+    pub fn token_planet_hop(ctx: Context<TokenPlanetHop>, amount: u64) -> Result<()> {
+        consume_hop_credit(&mut ctx.accounts.comet.ph)?;
+        // IMPORTANT VALIDATION: TO AND FROM CANNOT BE THE SAME
+        if ctx.accounts.from_planet.key() == ctx.accounts.to_planet.key() {
+            return Err(errors::ErrorCode::HopErrorToAndFromAreSame.into())
+        }
+
+        //Increment visits
+        add_visit(&mut ctx.accounts.to_planet)?;
+
+        // TRANSACTION - From planet vault to planet vault (signed by from planet)
+        let from_seed: [u8; 32] = planet_seed(&ctx.accounts.from_planet.name, &ctx.accounts.universe.mt);
+        let from_bump: [u8; 1] = [ctx.accounts.from_planet.bump];
+        let received: u64 = transfer_tokens(
+            &ctx.accounts.token_program,
+            &ctx.accounts.from_vault,
+            &mut ctx.accounts.to_vault,
+            ctx.accounts.from_planet.to_account_info(),
+            &ctx.accounts.mint,
+            amount,
+            &[&[PLANET_PDA_SEED_PRE, &from_seed, PLANET_PDA_SEED_POST, &from_bump]],
+        )?;
+
+        //Universe ledger and stats
+        record_transfer_fee(&mut ctx.accounts.universe, amount, received)?;
+        record_planet_hop(&mut ctx.accounts.universe)?;
+
+        emit!(PlanetHopped {
+            from_planet: ctx.accounts.from_planet.key(),
+            to_planet: ctx.accounts.to_planet.key(),
+            #[cfg(feature = "event-details")]
+            amount: received,
+        });
+        Ok(())
+    }
+
+    /// The withdraw fee (token units) goes to the universe treasury vault, the destination receives the rest.
+    pub fn token_withdraw(ctx: Context<TokenWithdrawAccounts>, amount: u64) -> Result<()> {
+        //VALIDATION
+        if ctx.accounts.from_vault.amount < amount {
+            return Err(errors::ErrorCode::PlanetNotEnoughFundsError.into())
+        }
+        let fee: u64 = ctx.accounts.universe.wfe;
+        let payout: u64 = withdraw_payout(amount, fee)?;
+
+        // TRANSACTIONS - From planet vault to destination token account and treasury vault (signed by planet)
+        let from_seed: [u8; 32] = planet_seed(&ctx.accounts.from_planet.name, &ctx.accounts.universe.mt);
+        let from_bump: [u8; 1] = [ctx.accounts.from_planet.bump];
+        let signer: &[&[&[u8]]] = &[&[PLANET_PDA_SEED_PRE, &from_seed, PLANET_PDA_SEED_POST, &from_bump]];
+        let from_planet: AccountInfo = ctx.accounts.from_planet.to_account_info();
+        #[cfg_attr(not(feature = "event-details"), allow(unused_variables))]
+        let received: u64 = transfer_tokens(&ctx.accounts.token_program, &ctx.accounts.from_vault, &mut ctx.accounts.destination, from_planet.clone(), &ctx.accounts.mint, payout, signer)?;
+        let fee_received: u64 = if fee > 0 {
+            transfer_tokens(&ctx.accounts.token_program, &ctx.accounts.from_vault, &mut ctx.accounts.treasury_vault, from_planet, &ctx.accounts.mint, fee, signer)?
+        } else {
+            0
+        };
+
+        //Universe ledger
+        record_withdraw(&mut ctx.accounts.universe, payout)?;
+        record_token_withdraw_fee(&mut ctx.accounts.universe, fee, fee_received)?;
+
+        emit!(Withdrawn {
+            from_planet: ctx.accounts.from_planet.key(),
+            #[cfg(feature = "event-details")]
+            destination: ctx.accounts.destination.key(),
+            #[cfg(feature = "event-details")]
+            amount: received,
+        });
+        Ok(())
+    }
+
+    /// Pays into the destination owner's associated token account, created (by the manager) when missing.
+    pub fn token_withdraw_to_ata(ctx: Context<TokenWithdrawToAtaAccounts>, amount: u64) -> Result<()> {
+        //VALIDATION
+        if ctx.accounts.from_vault.amount < amount {
+            return Err(errors::ErrorCode::PlanetNotEnoughFundsError.into())
+        }
+        let fee: u64 = ctx.accounts.universe.wfe;
+        let payout: u64 = withdraw_payout(amount, fee)?;
+
+        // TRANSACTIONS - From planet vault to destination associated token account and treasury vault (signed by planet)
+        let from_seed: [u8; 32] = planet_seed(&ctx.accounts.from_planet.name, &ctx.accounts.universe.mt);
+        let from_bump: [u8; 1] = [ctx.accounts.from_planet.bump];
+        let signer: &[&[&[u8]]] = &[&[PLANET_PDA_SEED_PRE, &from_seed, PLANET_PDA_SEED_POST, &from_bump]];
+        let from_planet: AccountInfo = ctx.accounts.from_planet.to_account_info();
+        #[cfg_attr(not(feature = "event-details"), allow(unused_variables))]
+        let received: u64 = transfer_tokens(&ctx.accounts.token_program, &ctx.accounts.from_vault, &mut ctx.accounts.destination, from_planet.clone(), &ctx.accounts.mint, payout, signer)?;
+        let fee_received: u64 = if fee > 0 {
+            transfer_tokens(&ctx.accounts.token_program, &ctx.accounts.from_vault, &mut ctx.accounts.treasury_vault, from_planet, &ctx.accounts.mint, fee, signer)?
+        } else {
+            0
+        };
+
+        //Universe ledger
+        record_withdraw(&mut ctx.accounts.universe, payout)?;
+        record_token_withdraw_fee(&mut ctx.accounts.universe, fee, fee_received)?;
+
+        emit!(Withdrawn {
+            from_planet: ctx.accounts.from_planet.key(),
+            #[cfg(feature = "event-details")]
+            destination: ctx.accounts.destination.key(),
+            #[cfg(feature = "event-details")]
+            amount: received,
+        });
+        Ok(())
+    }
+
+    ///-------------------------------------------------------------------///
+    /// WSOL DEPOSIT / WITHDRAW
+    /// Native SOL enters and leaves the wSOL universe without users managing wrapped SOL,
+    /// so native and wSOL liquidity share the same planets.
+    /// - Deposit: lamports go straight into the planet vault and are synced to wSOL
+    /// - Withdraw: wSOL moves to a temporary account owned by the planet, which is closed
+    ///   to the manager (unwrap), and the manager forwards the lamports to the destination
+    ///-------------------------------------------------------------------///
+    pub fn new_token_comet_sol(ctx: Context<CreateTokenCometSol>, deposit_lamports: u64) -> Result<()> {
+        assert_denomination(&ctx.accounts.universe, deposit_lamports)?;
+        let fee: u64 = ctx.accounts.universe.cfe;
+        if fee >= deposit_lamports && fee > 0 {
+            return Err(errors::ErrorCode::FeeExceedsAmount.into())
+        }
+        let balance_before: u64 = ctx.accounts.planet_vault.amount;
+
+        // DEPOSIT TRANSFERS - From user to planet vault and treasury vault, then wrap
+        wrap_lamports(&ctx.accounts.creator, &ctx.accounts.planet_vault, &ctx.accounts.system_program, &ctx.accounts.token_program, checked_sub(deposit_lamports, fee)?)?;
+        if fee > 0 {
+            wrap_lamports(&ctx.accounts.creator, &ctx.accounts.treasury_vault, &ctx.accounts.system_program, &ctx.accounts.token_program, fee)?;
+        }
+        ctx.accounts.planet_vault.reload()?;
+        let received: u64 = checked_sub(ctx.accounts.planet_vault.amount, balance_before)?;
+
+        //Universe ledger
+        record_deposit(&mut ctx.accounts.universe, checked_sum(&[received, fee])?)?;
+        record_fee(&mut ctx.accounts.universe, fee)?;
+
+        emit!(CometCreated {
+            planet: ctx.accounts.planet.key(),
+            #[cfg(feature = "event-details")]
+            amount: received,
+        });
+        Ok(())
+    }
+
+    pub fn token_withdraw_sol(ctx: Context<TokenWithdrawSolAccounts>, amount: u64) -> Result<()> {
+        //VALIDATION
+        if ctx.accounts.from_vault.amount < amount {
+            return Err(errors::ErrorCode::PlanetNotEnoughFundsError.into())
+        }
+        let fee: u64 = ctx.accounts.universe.wfe;
+        let payout: u64 = withdraw_payout(amount, fee)?;
+
+        // TRANSACTIONS - From planet vault to the unwrap account and treasury vault (signed by planet)
+        let from_seed: [u8; 32] = planet_seed(&ctx.accounts.from_planet.name, &ctx.accounts.universe.mt);
+        let from_bump: [u8; 1] = [ctx.accounts.from_planet.bump];
+        let signer: &[&[&[u8]]] = &[&[PLANET_PDA_SEED_PRE, &from_seed, PLANET_PDA_SEED_POST, &from_bump]];
+        let from_planet: AccountInfo = ctx.accounts.from_planet.to_account_info();
+        let unwrapped: u64 = transfer_tokens(&ctx.accounts.token_program, &ctx.accounts.from_vault, &mut ctx.accounts.unwrap, from_planet.clone(), &ctx.accounts.mint, payout, signer)?;
+        let fee_received: u64 = if fee > 0 {
+            transfer_tokens(&ctx.accounts.token_program, &ctx.accounts.from_vault, &mut ctx.accounts.treasury_vault, from_planet, &ctx.accounts.mint, fee, signer)?
+        } else {
+            0
+        };
+
+        // UNWRAP - Close the unwrap account to the manager (rent + unwrapped lamports)
+        let close_accounts = token_interface::CloseAccount {
+            account: ctx.accounts.unwrap.to_account_info(),
+            destination: ctx.accounts.manager.to_account_info(),
+            authority: ctx.accounts.from_planet.to_account_info(),
+        };
+        token_interface::close_account(CpiContext::new_with_signer(ctx.accounts.token_program.to_account_info(), close_accounts, signer))?;
+
+        // TRANSACTION - From manager to destination
+        let transfer_accounts = system_program::Transfer {
+            from: ctx.accounts.manager.to_account_info(),
+            to: ctx.accounts.destination.to_account_info(),
+        };
+        system_program::transfer(CpiContext::new(ctx.accounts.system_program.to_account_info(), transfer_accounts), unwrapped)?;
+
+        //Universe ledger
+        record_withdraw(&mut ctx.accounts.universe, payout)?;
+        record_token_withdraw_fee(&mut ctx.accounts.universe, fee, fee_received)?;
+
+        emit!(Withdrawn {
+            from_planet: ctx.accounts.from_planet.key(),
+            #[cfg(feature = "event-details")]
+            destination: ctx.accounts.destination.key(),
+            #[cfg(feature = "event-details")]
+            amount: unwrapped,
+        });
+        Ok(())
+    }
+
+    /// Stars record the amount their vault actually received.
+    pub fn token_star_hop_two_start(ctx: Context<TokenStarHopTwoStart>, deposit: u64) -> Result<()> {
+        consume_hop_credit(&mut ctx.accounts.comet.s2)?;
+        let clock: Clock = Clock::get().unwrap();
+        let origin: Pubkey = ctx.accounts.from_planet.key();
+        let manager: Pubkey = ctx.accounts.manager.key();
+        let mint: Pubkey = ctx.accounts.mint.key();
+        let star_id: u64 = ctx.accounts.universe.sc;
+
+        // GET DEPOSIT SPLIT AMOUNT
+        let (star_one_amount, star_two_amount) = split_deposit_two(deposit, get_random_percent()?)?;
+
+        // TRANSACTIONS - From planet vault to star vaults (signed by from planet)
+        let from_seed: [u8; 32] = planet_seed(&ctx.accounts.from_planet.name, &ctx.accounts.universe.mt);
+        let from_bump: [u8; 1] = [ctx.accounts.from_planet.bump];
+        let signer: &[&[&[u8]]] = &[&[PLANET_PDA_SEED_PRE, &from_seed, PLANET_PDA_SEED_POST, &from_bump]];
+        let from_planet: AccountInfo = ctx.accounts.from_planet.to_account_info();
+        let star_one_received: u64 = transfer_tokens(&ctx.accounts.token_program, &ctx.accounts.from_vault, &mut ctx.accounts.star_one_vault, from_planet.clone(), &ctx.accounts.mint, star_one_amount, signer)?;
+        let star_two_received: u64 = transfer_tokens(&ctx.accounts.token_program, &ctx.accounts.from_vault, &mut ctx.accounts.star_two_vault, from_planet, &ctx.accounts.mint, star_two_amount, signer)?;
+
+        light_star(&mut ctx.accounts.star_one, star_id, star_one_received, manager, origin, clock.unix_timestamp);
+        light_star(&mut ctx.accounts.star_two, star_id_offset(star_id, 1)?, star_two_received, manager, origin, clock.unix_timestamp);
+        ctx.accounts.star_one.mint = mint;
+        ctx.accounts.star_two.mint = mint;
+        ctx.accounts.star_one.bump = ctx.bumps.star_one;
+        ctx.accounts.star_two.bump = ctx.bumps.star_two;
+
+        //Move the star counter past the used ids
+        ctx.accounts.universe.sc = star_id_offset(star_id, 2)?;
+
+        //Universe ledger and stats
+        record_transfer_fee(&mut ctx.accounts.universe, deposit, checked_sum(&[star_one_received, star_two_received])?)?;
+        open_stars(&mut ctx.accounts.universe, 2)?;
+        record_star_hop(&mut ctx.accounts.universe, 2)?;
+
+        emit!(StarHopStarted {
+            from_planet: origin,
+            stars: vec![ctx.accounts.star_one.key(), ctx.accounts.star_two.key()],
+            #[cfg(feature = "event-details")]
+            amounts: vec![star_one_received, star_two_received],
+        });
+        Ok(())
+    }
+
+    /// Deposit is the sum of the star amounts (received at start).
+    pub fn token_star_hop_two_end(ctx: Context<TokenStarHopTwoEnd>, deposit: u64) -> Result<()> {
+        if checked_sum(&[ctx.accounts.star_one.amount, ctx.accounts.star_two.amount])? != deposit {
+            return Err(errors::ErrorCode::StarHopCalculationError.into())
+        }
+
+        //Clear our star amount
+        ctx.accounts.star_one.amount = 0;
+        ctx.accounts.star_two.amount = 0;
+
+        //Increment planet visit
+        add_visit(&mut ctx.accounts.to_planet)?;
+
+        // TRANSACTIONS - Star vaults to destination planet vault (signed by each star)
+        // The whole vault balance lands so the vaults can be closed.
+        let held: u64 = checked_sum(&[ctx.accounts.star_one_vault.amount, ctx.accounts.star_two_vault.amount])?;
+        let manager: AccountInfo = ctx.accounts.manager.to_account_info();
+        let star_one_seed: [u8; 32] = star_seed(ctx.accounts.star_one.id, &ctx.accounts.star_one.mint);
+        let star_one_bump: [u8; 1] = [ctx.accounts.star_one.bump];
+        let star_one_signer: &[&[&[u8]]] = &[&[STAR_SEED_PRE, &star_one_seed, STAR_SEED_POST, &star_one_bump]];
+        let star_one: AccountInfo = ctx.accounts.star_one.to_account_info();
+        let star_one_landed: u64 = transfer_tokens(&ctx.accounts.token_program, &ctx.accounts.star_one_vault, &mut ctx.accounts.to_vault, star_one.clone(), &ctx.accounts.mint, ctx.accounts.star_one_vault.amount, star_one_signer)?;
+        close_star_vault(&ctx.accounts.token_program, &ctx.accounts.mint, &ctx.accounts.star_one_vault, star_one, manager.clone(), star_one_signer)?;
+
+        let star_two_seed: [u8; 32] = star_seed(ctx.accounts.star_two.id, &ctx.accounts.star_two.mint);
+        let star_two_bump: [u8; 1] = [ctx.accounts.star_two.bump];
+        let star_two_signer: &[&[&[u8]]] = &[&[STAR_SEED_PRE, &star_two_seed, STAR_SEED_POST, &star_two_bump]];
+        let star_two: AccountInfo = ctx.accounts.star_two.to_account_info();
+        let star_two_landed: u64 = transfer_tokens(&ctx.accounts.token_program, &ctx.accounts.star_two_vault, &mut ctx.accounts.to_vault, star_two.clone(), &ctx.accounts.mint, ctx.accounts.star_two_vault.amount, star_two_signer)?;
+        close_star_vault(&ctx.accounts.token_program, &ctx.accounts.mint, &ctx.accounts.star_two_vault, star_two, manager, star_two_signer)?;
+
+        //Universe ledger
+        let landed: u64 = checked_sum(&[star_one_landed, star_two_landed])?;
+        record_transfer_fee(&mut ctx.accounts.universe, held, landed)?;
+        close_stars(&mut ctx.accounts.universe, 2)?;
+
+        emit!(StarHopEnded {
+            to_planets: vec![ctx.accounts.to_planet.key()],
+            stars: vec![ctx.accounts.star_one.key(), ctx.accounts.star_two.key()],
+            #[cfg(feature = "event-details")]
+            amount: landed,
+        });
+        Ok(())
+    }
+
+    /// Stars record the amount their vault actually received.
+    pub fn token_star_hop_three_start(ctx: Context<TokenStarHopThreeStart>, deposit: u64) -> Result<()> {
+        consume_hop_credit(&mut ctx.accounts.comet.s3)?;
+        let clock: Clock = Clock::get().unwrap();
+        let origin: Pubkey = ctx.accounts.from_planet.key();
+        let manager: Pubkey = ctx.accounts.manager.key();
+        let mint: Pubkey = ctx.accounts.mint.key();
+        let star_id: u64 = ctx.accounts.universe.sc;
+
+        // GET DEPOSIT SPLIT AMOUNTS
+        let (star_one_amount, star_two_amount, star_three_amount) = split_deposit_three(deposit, get_random_percent()?)?;
+
+        // TRANSACTIONS - From planet vault to star vaults (signed by from planet)
+        let from_seed: [u8; 32] = planet_seed(&ctx.accounts.from_planet.name, &ctx.accounts.universe.mt);
+        let from_bump: [u8; 1] = [ctx.accounts.from_planet.bump];
+        let signer: &[&[&[u8]]] = &[&[PLANET_PDA_SEED_PRE, &from_seed, PLANET_PDA_SEED_POST, &from_bump]];
+        let from_planet: AccountInfo = ctx.accounts.from_planet.to_account_info();
+        let star_one_received: u64 = transfer_tokens(&ctx.accounts.token_program, &ctx.accounts.from_vault, &mut ctx.accounts.star_one_vault, from_planet.clone(), &ctx.accounts.mint, star_one_amount, signer)?;
+        let star_two_received: u64 = transfer_tokens(&ctx.accounts.token_program, &ctx.accounts.from_vault, &mut ctx.accounts.star_two_vault, from_planet.clone(), &ctx.accounts.mint, star_two_amount, signer)?;
+        let star_three_received: u64 = transfer_tokens(&ctx.accounts.token_program, &ctx.accounts.from_vault, &mut ctx.accounts.star_three_vault, from_planet, &ctx.accounts.mint, star_three_amount, signer)?;
+
+        light_star(&mut ctx.accounts.star_one, star_id, star_one_received, manager, origin, clock.unix_timestamp);
+        light_star(&mut ctx.accounts.star_two, star_id_offset(star_id, 1)?, star_two_received, manager, origin, clock.unix_timestamp);
+        light_star(&mut ctx.accounts.star_three, star_id_offset(star_id, 2)?, star_three_received, manager, origin, clock.unix_timestamp);
+        ctx.accounts.star_one.mint = mint;
+        ctx.accounts.star_two.mint = mint;
+        ctx.accounts.star_three.mint = mint;
+        ctx.accounts.star_one.bump = ctx.bumps.star_one;
+        ctx.accounts.star_two.bump = ctx.bumps.star_two;
+        ctx.accounts.star_three.bump = ctx.bumps.star_three;
+
+        //Move the star counter past the used ids
+        ctx.accounts.universe.sc = star_id_offset(star_id, 3)?;
+
+        //Universe ledger and stats
+        record_transfer_fee(&mut ctx.accounts.universe, deposit, checked_sum(&[star_one_received, star_two_received, star_three_received])?)?;
+        open_stars(&mut ctx.accounts.universe, 3)?;
+        record_star_hop(&mut ctx.accounts.universe, 3)?;
+
+        emit!(StarHopStarted {
+            from_planet: origin,
+            stars: vec![ctx.accounts.star_one.key(), ctx.accounts.star_two.key(), ctx.accounts.star_three.key()],
+            #[cfg(feature = "event-details")]
+            amounts: vec![star_one_received, star_two_received, star_three_received],
+        });
+        Ok(())
+    }
+
+    /// Deposit is the sum of the star amounts (received at start).
+    pub fn token_star_hop_three_end(ctx: Context<TokenStarHopThreeEnd>, deposit: u64) -> Result<()> {
+        if checked_sum(&[ctx.accounts.star_one.amount, ctx.accounts.star_two.amount, ctx.accounts.star_three.amount])? != deposit {
+            return Err(errors::ErrorCode::StarHopCalculationError.into())
+        }
+
+        //Clear our star amount
+        ctx.accounts.star_one.amount = 0;
+        ctx.accounts.star_two.amount = 0;
+        ctx.accounts.star_three.amount = 0;
+
+        //Increment planet visit
+        add_visit(&mut ctx.accounts.to_planet)?;
+
+        // TRANSACTIONS - Star vaults to destination planet vault (signed by each star)
+        // The whole vault balance lands so the vaults can be closed.
+        let held: u64 = checked_sum(&[ctx.accounts.star_one_vault.amount, ctx.accounts.star_two_vault.amount, ctx.accounts.star_three_vault.amount])?;
+        let manager: AccountInfo = ctx.accounts.manager.to_account_info();
+        let star_one_seed: [u8; 32] = star_seed(ctx.accounts.star_one.id, &ctx.accounts.star_one.mint);
+        let star_one_bump: [u8; 1] = [ctx.accounts.star_one.bump];
+        let star_one_signer: &[&[&[u8]]] = &[&[STAR_SEED_PRE, &star_one_seed, STAR_SEED_POST, &star_one_bump]];
+        let star_one: AccountInfo = ctx.accounts.star_one.to_account_info();
+        let star_one_landed: u64 = transfer_tokens(&ctx.accounts.token_program, &ctx.accounts.star_one_vault, &mut ctx.accounts.to_vault, star_one.clone(), &ctx.accounts.mint, ctx.accounts.star_one_vault.amount, star_one_signer)?;
+        close_star_vault(&ctx.accounts.token_program, &ctx.accounts.mint, &ctx.accounts.star_one_vault, star_one, manager.clone(), star_one_signer)?;
+
+        let star_two_seed: [u8; 32] = star_seed(ctx.accounts.star_two.id, &ctx.accounts.star_two.mint);
+        let star_two_bump: [u8; 1] = [ctx.accounts.star_two.bump];
+        let star_two_signer: &[&[&[u8]]] = &[&[STAR_SEED_PRE, &star_two_seed, STAR_SEED_POST, &star_two_bump]];
+        let star_two: AccountInfo = ctx.accounts.star_two.to_account_info();
+        let star_two_landed: u64 = transfer_tokens(&ctx.accounts.token_program, &ctx.accounts.star_two_vault, &mut ctx.accounts.to_vault, star_two.clone(), &ctx.accounts.mint, ctx.accounts.star_two_vault.amount, star_two_signer)?;
+        close_star_vault(&ctx.accounts.token_program, &ctx.accounts.mint, &ctx.accounts.star_two_vault, star_two, manager.clone(), star_two_signer)?;
+
+        let star_three_seed: [u8; 32] = star_seed(ctx.accounts.star_three.id, &ctx.accounts.star_three.mint);
+        let star_three_bump: [u8; 1] = [ctx.accounts.star_three.bump];
+        let star_three_signer: &[&[&[u8]]] = &[&[STAR_SEED_PRE, &star_three_seed, STAR_SEED_POST, &star_three_bump]];
+        let star_three: AccountInfo = ctx.accounts.star_three.to_account_info();
+        let star_three_landed: u64 = transfer_tokens(&ctx.accounts.token_program, &ctx.accounts.star_three_vault, &mut ctx.accounts.to_vault, star_three.clone(), &ctx.accounts.mint, ctx.accounts.star_three_vault.amount, star_three_signer)?;
+        close_star_vault(&ctx.accounts.token_program, &ctx.accounts.mint, &ctx.accounts.star_three_vault, star_three, manager, star_three_signer)?;
+
+        //Universe ledger
+        let landed: u64 = checked_sum(&[star_one_landed, star_two_landed, star_three_landed])?;
+        record_transfer_fee(&mut ctx.accounts.universe, held, landed)?;
+        close_stars(&mut ctx.accounts.universe, 3)?;
+
+        emit!(StarHopEnded {
+            to_planets: vec![ctx.accounts.to_planet.key()],
+            stars: vec![ctx.accounts.star_one.key(), ctx.accounts.star_two.key(), ctx.accounts.star_three.key()],
+            #[cfg(feature = "event-details")]
+            amount: landed,
+        });
+        Ok(())
+    }
+
+
+    ///-------------------------------------------------------------------///
+    /// REPORT PLANETS
+    /// Read-only balance report. Pass the registered planets to report on through `remaining_accounts`.
+    /// - Target is the equal share of the reported balances
+    /// - Each planet is flagged when over the target and when over the universe cap
+    /// The report is returned through return data.
+    ///-------------------------------------------------------------------///
+    pub fn report_planets(ctx: Context<ReportPlanets>) -> Result<PlanetReport>{
+        let universe: &Account<Universe> = &ctx.accounts.universe;
+        let mut planets: Vec<PlanetBalance> = Vec::new();
+
+        for account in ctx.remaining_accounts.iter() {
+            if account.owner != ctx.program_id || planets.iter().any(|p| p.planet == *account.key) {
+                return Err(errors::ErrorCode::PlanetReportInvalidAccount.into())
+            }
+            let data = account.try_borrow_data()?;
+            if !data.starts_with(&Planet::DISCRIMINATOR) {
+                return Err(errors::ErrorCode::PlanetReportInvalidAccount.into())
+            }
+            let planet: Planet = Planet::try_deserialize(&mut &data[..])?;
+            if planet.pda != *account.key || planet.universe != universe.key() || !universe.p.contains(&planet.name) {
+                return Err(errors::ErrorCode::PlanetReportInvalidAccount.into())
+            }
+            planets.push(PlanetBalance {
+                planet: *account.key,
+                balance: spendable_lamports(account)?,
+                over_target: false,
+                over_cap: false,
+            });
+        }
+
+        let balances: Vec<u64> = planets.iter().map(|p| p.balance).collect();
+        let target: u64 = checked_sum(&balances)?.checked_div(planets.len() as u64).unwrap_or(0);
+        for planet in planets.iter_mut() {
+            planet.over_target = planet.balance > target;
+            planet.over_cap = universe.mb != 0 && planet.balance > universe.mb;
+        }
+
+        Ok(PlanetReport {
+            cap: universe.mb,
+            target,
+            planets,
+        })
+    }
+
+
+    ///-------------------------------------------------------------------///
+    /// VERIFY INVARIANTS
+    /// Read-only conservation check. Pass every registered planet and every open star
+    /// of the universe through `remaining_accounts` (any order).
+    /// - All accounts must be owned by the program and planets must be registered
+    /// - Spendable lamports (above rent) of the universe + planets + staked planet lamports + star amounts
+    ///   must equal deposits - withdrawals + rewards - swept - stake shortfall from the universe ledger
+    /// - The universe itself must hold exactly fees + rewards - swept
+    /// Lamports sent directly to the universe or a planet (outside of the program) make this check fail.
+    /// Totals are returned through return data.
+    ///-------------------------------------------------------------------///
+    pub fn verify_invariants(ctx: Context<VerifyInvariants>) -> Result<InvariantTotals>{
+        let universe: &Account<Universe> = &ctx.accounts.universe;
+        let mut seen: Vec<Pubkey> = Vec::new();
+        let mut planet_count: usize = 0;
+        let mut star_count: u64 = 0;
+        let mut planet_lamports: u64 = 0;
+        let mut staked_lamports: u64 = 0;
+        let mut star_lamports: u64 = 0;
+
+        for account in ctx.remaining_accounts.iter() {
+            if account.owner != ctx.program_id {
+                return Err(errors::ErrorCode::InvariantAccountNotOwned.into())
+            }
+            if seen.contains(account.key) {
+                return Err(errors::ErrorCode::InvariantDuplicateAccount.into())
+            }
+            seen.push(*account.key);
+
+            let data = account.try_borrow_data()?;
+            if data.starts_with(&Planet::DISCRIMINATOR) {
+                let planet: Planet = Planet::try_deserialize(&mut &data[..])?;
+                if planet.pda != *account.key || planet.universe != universe.key() || !universe.p.contains(&planet.name) {
+                    return Err(errors::ErrorCode::InvariantPlanetNotRegistered.into())
+                }
+                let spendable: u64 = checked_sub(account.lamports(), rent_exempt_minimum(account)?)?;
+                planet_lamports = checked_sum(&[planet_lamports, spendable])?;
+                staked_lamports = checked_sum(&[staked_lamports, planet.sk])?;
+                planet_count += 1;
+            } else if data.starts_with(&Star::DISCRIMINATOR) {
+                let star: Star = Star::try_deserialize(&mut &data[..])?;
+                if !star.in_use || star.mint != Pubkey::default() {
+                    continue
+                }
+                //The star must hold exactly its recorded amount above rent
+                let held: u64 = checked_sub(account.lamports(), rent_exempt_minimum(account)?)?;
+                if held != star.amount {
+                    return Err(errors::ErrorCode::InvariantLedgerMismatch.into())
+                }
+                star_lamports = checked_sum(&[star_lamports, star.amount])?;
+                star_count += 1;
+            } else {
+                return Err(errors::ErrorCode::InvariantAccountNotOwned.into())
+            }
+        }
+
+        //Every registered planet and every open star must be accounted for
+        if planet_count != universe.p.len() || star_count != universe.ns {
+            return Err(errors::ErrorCode::InvariantMissingAccounts.into())
+        }
+
+        //The universe holds the fees and staking rewards that were not swept yet
+        let universe_info: AccountInfo = universe.to_account_info();
+        let universe_lamports: u64 = universe_info.lamports().saturating_sub(rent_exempt_minimum(&universe_info)?);
+        let held: u64 = checked_sub(checked_sum(&[universe.tf, universe.rw])?, universe.ts)?;
+
+        let expected: u64 = checked_sub(checked_sub(checked_sum(&[universe.td, universe.rw])?, universe.tw)?, checked_sum(&[universe.sl, universe.ts])?)?;
+        let total: u64 = checked_sum(&[universe_lamports, planet_lamports, staked_lamports, star_lamports])?;
+        if staked_lamports != universe.sk || universe_lamports != held || total != expected {
+            return Err(errors::ErrorCode::InvariantLedgerMismatch.into())
+        }
+
+        Ok(InvariantTotals {
+            universe: universe_lamports,
+            planets: planet_lamports,
+            stars: star_lamports,
+            deposited: universe.td,
+            withdrawn: universe.tw,
+            fees: universe.tf,
+            staked: staked_lamports,
+            rewards: universe.rw,
+            shortfall: universe.sl,
+            swept: universe.ts,
+        })
+    }
+
+
+    ///-------------------------------------------------------------------///
+    /// VERIFY TOKEN INVARIANTS
+    /// Token version of verify_invariants. Pass every registered planet and every open token star
+    /// of the universe through `remaining_accounts`, each followed by its vault (any order).
+    /// - Vaults must be the associated token accounts of the preceding planet or star
+    /// - Each star vault must hold exactly its recorded amount
+    /// - Planet vaults + star vaults must equal deposits - withdrawals - fees - withheld transfer fees
+    /// Tokens sent directly to a vault (outside of the program) make this check fail.
+    /// Totals are returned through return data.
+    ///-------------------------------------------------------------------///
+    pub fn verify_token_invariants(ctx: Context<VerifyTokenInvariants>) -> Result<TokenInvariantTotals>{
+        let universe: &Account<Universe> = &ctx.accounts.universe;
+        let mint: AccountInfo = ctx.accounts.mint.to_account_info();
+        let mut seen: Vec<Pubkey> = Vec::new();
+        let mut planet_count: usize = 0;
+        let mut star_count: u64 = 0;
+        let mut planet_tokens: u64 = 0;
+        let mut star_tokens: u64 = 0;
+
+        for pair in ctx.remaining_accounts.chunks(2) {
+            let [account, vault] = pair else {
+                return Err(errors::ErrorCode::InvariantMissingAccounts.into())
+            };
+            if account.owner != ctx.program_id {
+                return Err(errors::ErrorCode::InvariantAccountNotOwned.into())
+            }
+            if seen.contains(account.key) {
+                return Err(errors::ErrorCode::InvariantDuplicateAccount.into())
+            }
+            seen.push(*account.key);
+            let held: u64 = token_vault_amount(vault, account.key, &mint)?;
+
+            let data = account.try_borrow_data()?;
+            if data.starts_with(&Planet::DISCRIMINATOR) {
+                let planet: Planet = Planet::try_deserialize(&mut &data[..])?;
+                if planet.pda != *account.key || planet.universe != universe.key() || !universe.p.contains(&planet.name) {
+                    return Err(errors::ErrorCode::InvariantPlanetNotRegistered.into())
+                }
+                planet_tokens = checked_sum(&[planet_tokens, held])?;
+                planet_count += 1;
+            } else if data.starts_with(&Star::DISCRIMINATOR) {
+                let star: Star = Star::try_deserialize(&mut &data[..])?;
+                if !star.in_use || star.mint != universe.mt {
+                    continue
+                }
+                if held != star.amount {
+                    return Err(errors::ErrorCode::InvariantLedgerMismatch.into())
+                }
+                star_tokens = checked_sum(&[star_tokens, star.amount])?;
+                star_count += 1;
+            } else {
+                return Err(errors::ErrorCode::InvariantAccountNotOwned.into())
+            }
+        }
+
+        //Every registered planet and every open star must be accounted for
+        if planet_count != universe.p.len() || star_count != universe.ns {
+            return Err(errors::ErrorCode::InvariantMissingAccounts.into())
+        }
+
+        let expected: u64 = checked_sub(checked_sub(checked_sub(universe.td, universe.tw)?, universe.tf)?, universe.tl)?;
+        if checked_sum(&[planet_tokens, star_tokens])? != expected {
+            return Err(errors::ErrorCode::InvariantLedgerMismatch.into())
+        }
+
+        Ok(TokenInvariantTotals {
+            planets: planet_tokens,
+            stars: star_tokens,
+            deposited: universe.td,
+            withdrawn: universe.tw,
+            fees: universe.tf,
+            withheld: universe.tl,
+            swept: universe.ts,
+        })
+    }
+
 }
@@ -1,30 +1,963 @@
 use super::*;
-pub fn get_planet_program_address(planet_name: &String, program_id: &Pubkey) -> Pubkey {
+use anchor_lang::solana_program::hash::hashv;
+use anchor_lang::solana_program::native_token::LAMPORTS_PER_SOL;
+use anchor_spl::associated_token::get_associated_token_address_with_program_id;
+use anchor_spl::token_2022::spl_token_2022::{self, extension::{BaseStateWithExtensions, StateWithExtensions, transfer_fee::{TransferFeeAmount, TransferFeeConfig}}};
+use anchor_spl::token_interface::{self, CloseAccount, HarvestWithheldTokensToMint, Mint, SyncNative, TokenAccount, TokenInterface, TransferChecked};
+
+///-------------------------------------------------------------//
+/// SEEDS AND ADDRESSES
+/// Universes are seeded by mint (`NATIVE_MINT_SENTINEL` for SOL).
+/// Planet and star seeds are the sha256 of the universe mint and the identifier (32 bytes),
+/// so every universe has its own planets and stars, identifiers are not capped by the
+/// 32 byte seed limit and their format does not leak.
+/// These helpers are shared with `oridion-client` so derivation cannot drift.
+///-------------------------------------------------------------//
+/// sha256 of the universe mint and the planet name.
+pub fn planet_seed(planet_name: &str, mint: &Pubkey) -> [u8; 32] {
+    hashv(&[mint.as_ref(), planet_name.as_bytes()]).to_bytes()
+}
+
+/// sha256 of the universe mint and the star id (universe star counter or pool index, little endian).
+pub fn star_seed(star_id: u64, mint: &Pubkey) -> [u8; 32] {
+    hashv(&[mint.as_ref(), &star_id.to_le_bytes()]).to_bytes()
+}
+
+pub fn get_universe_program_address(mint: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    let(pk, _pda_bump) = Pubkey::find_program_address(&[UNIVERSE_PDA_SEED, mint.as_ref()], program_id);
+    pk
+}
+
+pub fn get_planet_program_address(planet_name: &str, mint: &Pubkey, program_id: &Pubkey) -> Pubkey {
     let(pk, _pda_bump) = Pubkey::find_program_address(&[
         PLANET_PDA_SEED_PRE,
-        planet_name.as_ref(),
+        &planet_seed(planet_name, mint),
         PLANET_PDA_SEED_POST
     ], program_id);
     pk
 }
 
-pub fn get_random_percent() -> f32 {
-    let clock: Clock = Clock::get().unwrap();
-    // First get the percent to split deposit between two stars.
-    let clock_time_str: String = clock.unix_timestamp.to_string();
-    let percent: &str = {
-        let split_pos: usize = clock_time_str.char_indices().nth_back(1).unwrap().0;
-        &clock_time_str[split_pos..]
+pub fn get_star_program_address(star_id: u64, mint: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    let(pk, _pda_bump) = Pubkey::find_program_address(&[
+        STAR_SEED_PRE,
+        &star_seed(star_id, mint),
+        STAR_SEED_POST
+    ], program_id);
+    pk
+}
+
+pub fn get_pool_star_program_address(pool_index: u32, mint: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    let(pk, _pda_bump) = Pubkey::find_program_address(&[
+        STAR_POOL_SEED_PRE,
+        &star_seed(pool_index as u64, mint),
+        STAR_SEED_POST
+    ], program_id);
+    pk
+}
+
+/// Validates a new planet name and returns its seed.
+/// Names must be in canonical form (1 to 40 uppercase A-Z / 0-9 characters) so mixed-case
+/// duplicates like "Doraz"/"DORAZ" cannot exist, must not already be registered and must fit
+/// in the universe planet registry.
+/// Used in the `CreatePlanet` seeds so invalid names fail before the planet is initialized.
+pub fn planet_name_seed(name: &str, universe: &Universe) -> Result<[u8; 32]> {
+    if name.is_empty() || name.len() > PLANET_NAME_MAX_CHARS {
+        return Err(errors::ErrorCode::PlanetNameInvalidLength.into())
+    }
+    if name.chars().any(|c| c.is_ascii_lowercase()) {
+        return Err(errors::ErrorCode::PlanetNameNotCanonical.into())
+    }
+    if !name.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit()) {
+        return Err(errors::ErrorCode::PlanetNameInvalidCharacters.into())
+    }
+    if universe.p.iter().any(|planet| planet == name) {
+        return Err(errors::ErrorCode::PlanetAlreadyExists.into())
+    }
+
+    //Registry is a borsh Vec<String>: 4 byte length + (4 byte prefix + name) per planet
+    let registry_length: usize = STRING_LENGTH_PREFIX
+        + universe.p.iter().map(|planet| STRING_LENGTH_PREFIX + planet.len()).sum::<usize>()
+        + STRING_LENGTH_PREFIX + name.len();
+    if registry_length > PLANETS_VEC_LENGTH {
+        return Err(errors::ErrorCode::PlanetRegistryFull.into())
+    }
+    Ok(planet_seed(name, &universe.mt))
+}
+
+/// Rent exempt minimum for the account's actual size, read from the Rent sysvar.
+pub fn rent_exempt_minimum(account: &AccountInfo) -> Result<u64> {
+    Ok(Rent::get()?.minimum_balance(account.data_len()))
+}
+
+/// Rent floor guard. Must be called before every `sub_lamports` that moves funds
+/// out of an account that stays open, so planets and stars can never be drained below rent exemption.
+/// (Exploding a star drains it completely and is not guarded.)
+pub fn assert_rent_floor(account: &AccountInfo, debit: u64) -> Result<()> {
+    let minimum: u64 = rent_exempt_minimum(account)?;
+    let spendable: u64 = account.lamports().saturating_sub(minimum);
+    if debit > spendable {
+        msg!("Rent floor shortfall: {} lamports", debit.saturating_sub(spendable));
+        return Err(errors::ErrorCode::RentFloorShortfall.into())
+    }
+    Ok(())
+}
+
+/// Lamports held above the rent exempt minimum.
+pub fn spendable_lamports(account: &AccountInfo) -> Result<u64> {
+    checked_sub(account.lamports(), rent_exempt_minimum(account)?)
+}
+
+/// Planet balance cap guard. Must be called after every credit to a planet
+/// (deposits, hops, recoveries), so no planet holds more than the universe cap.
+/// A cap of 0 disables the check.
+pub fn assert_planet_cap(planet: &AccountInfo, universe: &Universe) -> Result<()> {
+    if universe.mb == 0 {
+        return Ok(())
+    }
+    let balance: u64 = spendable_lamports(planet)?;
+    if balance > universe.mb {
+        msg!("Planet balance cap exceeded by {} lamports", balance - universe.mb);
+        return Err(errors::ErrorCode::PlanetBalanceCapExceeded.into())
+    }
+    Ok(())
+}
+
+///-------------------------------------------------------------//
+/// FEES
+/// Universe fees are lamports, or USD micro-cents (1e-8 USD) when `fu` is set.
+/// USD fees are converted at charge time with the universe SOL/USD price account
+/// (Pyth v2 layout): the price must be trading, fresh and within the confidence limit.
+///-------------------------------------------------------------//
+/// Fee in lamports for a universe fee value (lamports, or USD micro-cents in USD mode).
+pub fn fee_lamports(fee: u64, universe: &Universe, oracle: Option<&AccountInfo>) -> Result<u64> {
+    if !universe.fu || fee == 0 {
+        return Ok(fee)
+    }
+    let oracle: &AccountInfo = oracle.ok_or(errors::ErrorCode::FeeOracleMissing)?;
+    if *oracle.key != universe.or {
+        return Err(errors::ErrorCode::FeeOracleMismatch.into())
+    }
+    let (price, expo) = read_oracle_price(oracle, universe)?;
+
+    //lamports = fee / MICRO_CENTS_PER_USD / (price * 10^expo) * LAMPORTS_PER_SOL
+    let scale: u128 = 10u128.pow(expo.unsigned_abs());
+    let (numerator_scale, denominator_scale): (u128, u128) = if expo <= 0 { (scale, 1) } else { (1, scale) };
+    let lamports: u128 = (fee as u128)
+        .checked_mul(LAMPORTS_PER_SOL as u128 * numerator_scale)
+        .zip((price as u128).checked_mul(MICRO_CENTS_PER_USD as u128 * denominator_scale))
+        .map(|(numerator, denominator)| numerator / denominator)
+        .ok_or(errors::ErrorCode::MathOverflow)?;
+    Ok(u64::try_from(lamports).map_err(|_| errors::ErrorCode::MathOverflow)?)
+}
+
+/// Aggregate SOL/USD price and exponent of a Pyth v2 price account, checked against the universe limits.
+pub fn read_oracle_price(oracle: &AccountInfo, universe: &Universe) -> Result<(u64, i32)> {
+    let data = oracle.try_borrow_data()?;
+    if data.len() < PYTH_MIN_ACCOUNT_LENGTH
+        || read_u32(&data, 0) != PYTH_MAGIC
+        || read_u32(&data, 4) != PYTH_VERSION
+        || read_u32(&data, 8) != PYTH_ACCOUNT_TYPE_PRICE
+        || read_u32(&data, PYTH_STATUS_OFFSET) != PYTH_STATUS_TRADING {
+        return Err(errors::ErrorCode::FeeOracleInvalidAccount.into())
+    }
+    let expo: i32 = read_u32(&data, PYTH_EXPO_OFFSET) as i32;
+    let price: i64 = read_u64(&data, PYTH_PRICE_OFFSET) as i64;
+    let conf: u64 = read_u64(&data, PYTH_CONF_OFFSET);
+    let timestamp: i64 = read_u64(&data, PYTH_TIMESTAMP_OFFSET) as i64;
+    if price <= 0 || expo.unsigned_abs() > PYTH_MAX_EXPONENT {
+        return Err(errors::ErrorCode::FeeOracleInvalidAccount.into())
+    }
+
+    let age: i64 = Clock::get()?.unix_timestamp.saturating_sub(timestamp);
+    if age > universe.os {
+        msg!("Oracle price is {} seconds old", age);
+        return Err(errors::ErrorCode::FeeOracleStale.into())
+    }
+    //conf / price <= oc / BPS_TOTAL
+    if (conf as u128) * (BPS_TOTAL as u128) > (price as u128) * (universe.oc as u128) {
+        return Err(errors::ErrorCode::FeeOracleConfidence.into())
+    }
+    Ok((price as u64, expo))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+/// Deposit fee (universe units) for a comet and its hop package: comet fee + hop fees per hop.
+pub fn comet_fee(universe: &Universe, hops: &HopPackage) -> Result<u64> {
+    let planet: u64 = (hops.planet as u64).checked_mul(universe.hpfe).ok_or(errors::ErrorCode::MathOverflow)?;
+    let star_two: u64 = (hops.star_two as u64).checked_mul(universe.hsfe2).ok_or(errors::ErrorCode::MathOverflow)?;
+    let star_three: u64 = (hops.star_three as u64).checked_mul(universe.hsfe3).ok_or(errors::ErrorCode::MathOverflow)?;
+    checked_sum(&[universe.cfe, planet, star_two, star_three])
+}
+
+/// Stranded star recovery guard: the star must be older than the universe star timeout and
+/// funds may only return to its origin planet or a registered fallback planet.
+pub fn assert_star_recoverable(universe: &Universe, star: &Star, to: &Account<Planet>) -> Result<()> {
+    let star_age: i64 = Clock::get()?.unix_timestamp.checked_sub(star.created).ok_or(errors::ErrorCode::MathOverflow)?;
+    if star_age < universe.sto {
+        return Err(errors::ErrorCode::StarRecoveryTimeoutNotReached.into())
+    }
+    let is_origin: bool = to.key() == star.origin;
+    let is_registered: bool = to.pda == to.key() && universe.p.contains(&to.name);
+    if !is_origin && !is_registered {
+        return Err(errors::ErrorCode::StarRecoveryInvalidPlanet.into())
+    }
+    Ok(())
+}
+
+/// Deposit flow of new_comet and new_comet_with_hops: creator -> galaxy -> planet.
+/// The fee (universe units, converted with the oracle in USD mode) is kept by the galaxy.
+pub fn deposit_comet(accounts: &mut CreateComet, deposit_lamports: u64, fee: u64) -> Result<()> {
+    assert_denomination(&accounts.universe, deposit_lamports)?;
+
+    // INCREMENT VISIT
+    add_visit(&mut accounts.planet)?;
+
+    // DEPOSIT TRANSFER - From creator to galaxy
+    // The creator signature (wallet or PDA signed by the calling program) carries through this CPI.
+    let transfer_accounts = system_program::Transfer {
+        from: accounts.creator.to_account_info(),
+        to: accounts.universe.to_account_info(),
     };
+    system_program::transfer(CpiContext::new(accounts.system_program.to_account_info(), transfer_accounts), deposit_lamports)?;
+
+    // FEE - Kept by the galaxy
+    let oracle: Option<AccountInfo> = accounts.oracle.as_ref().map(|oracle| oracle.to_account_info());
+    let fee: u64 = fee_lamports(fee, &accounts.universe, oracle.as_ref())?;
+    if fee >= deposit_lamports && fee > 0 {
+        return Err(errors::ErrorCode::FeeExceedsAmount.into())
+    }
+    let landed: u64 = checked_sub(deposit_lamports, fee)?;
 
-    //msg!("Lamports splitting: {}", deposit.to_string());
-    //let first_split_percent: f32 = f32::from_str(first_split_percent).unwrap();
-    let percent: f32 = percent.parse::<f32>().unwrap();
-    if percent < 10 as f32 {
-        return 10 as f32
-    } else if percent > 90 as f32 {
-        return 90 as f32
+    // TRANSACTION - From galaxy to planet
+    accounts.planet.add_lamports(landed)?;
+    assert_planet_cap(&accounts.planet.to_account_info(), &accounts.universe)?;
+    assert_rent_floor(&accounts.universe.to_account_info(), landed)?;
+    accounts.universe.sub_lamports(landed)?;
+
+    //Universe ledger
+    record_deposit(&mut accounts.universe, deposit_lamports)?;
+    record_fee(&mut accounts.universe, fee)
+}
+
+/// Token deposit flow of new_token_comet and new_token_comet_with_hops: the fee (token units)
+/// goes from the creator to the universe treasury vault, the rest to the planet vault.
+/// Returns the amount the planet vault received.
+pub fn deposit_token_comet(accounts: &mut CreateTokenComet, deposit: u64, fee: u64) -> Result<u64> {
+    assert_supported_mint(&accounts.mint)?;
+    assert_denomination(&accounts.universe, deposit)?;
+    if fee >= deposit && fee > 0 {
+        return Err(errors::ErrorCode::FeeExceedsAmount.into())
+    }
+
+    // TRANSACTIONS - From user token account to planet vault and treasury vault (signed by user)
+    let creator: AccountInfo = accounts.creator.to_account_info();
+    let received: u64 = transfer_tokens(&accounts.token_program, &accounts.source, &mut accounts.planet_vault, creator.clone(), &accounts.mint, checked_sub(deposit, fee)?, &[])?;
+    let fee_received: u64 = if fee > 0 {
+        transfer_tokens(&accounts.token_program, &accounts.source, &mut accounts.treasury_vault, creator, &accounts.mint, fee, &[])?
     } else {
-        return percent
+        0
+    };
+
+    //Universe ledger (what reached the vaults)
+    record_deposit(&mut accounts.universe, checked_sum(&[received, fee_received])?)?;
+    record_fee(&mut accounts.universe, fee_received)?;
+    Ok(received)
+}
+
+/// Deposit denominations: when the universe has denominations, deposits must be exactly one of them.
+pub fn assert_denomination(universe: &Universe, amount: u64) -> Result<()> {
+    if !universe.dn.is_empty() && !universe.dn.contains(&amount) {
+        return Err(errors::ErrorCode::DepositNotADenomination.into())
+    }
+    Ok(())
+}
+
+/// Deposit denominations: at most MAX_DENOMINATIONS, above zero and strictly increasing.
+pub fn assert_valid_denominations(denominations: &[u64]) -> Result<()> {
+    if denominations.len() > MAX_DENOMINATIONS
+        || denominations.first().is_some_and(|first| *first == 0)
+        || denominations.windows(2).any(|pair| pair[0] >= pair[1]) {
+        return Err(errors::ErrorCode::InvalidDenominations.into())
+    }
+    Ok(())
+}
+
+/// Prepaid hop credits: stores a bought hop package on its new comet.
+pub fn open_comet(comet: &mut Comet, universe: Pubkey, creator: Pubkey, hops: &HopPackage, bump: u8) {
+    comet.universe = universe;
+    comet.creator = creator;
+    comet.id = hops.id;
+    comet.ph = hops.planet;
+    comet.s2 = hops.star_two;
+    comet.s3 = hops.star_three;
+    comet.bump = bump;
+}
+
+/// Prepaid hop credits: consumes one credit of a comet (rejects when none remain).
+pub fn consume_hop_credit(credits: &mut u16) -> Result<()> {
+    *credits = credits.checked_sub(1).ok_or(errors::ErrorCode::NoHopCredits)?;
+    Ok(())
+}
+
+/// Withdraw and invoke: account metas and infos of the callback.
+/// The runtime rejects a call that sees only some of the balances changed by the withdrawal
+/// (UnbalancedInstruction), so the destination must be one of the remaining accounts and the
+/// other changed accounts (planet, universe) are appended read-only when not passed already.
+pub fn invoke_accounts<'info>(remaining: &[AccountInfo<'info>], destination: &Pubkey, changed: &[AccountInfo<'info>]) -> Result<(Vec<AccountMeta>, Vec<AccountInfo<'info>>)> {
+    if remaining.iter().any(|account| account.is_signer) {
+        return Err(errors::ErrorCode::InvokeSignerNotAllowed.into())
+    }
+    if !remaining.iter().any(|account| account.key == destination) {
+        return Err(errors::ErrorCode::InvokeDestinationMissing.into())
+    }
+    let mut metas: Vec<AccountMeta> = remaining.iter().map(|account| AccountMeta {
+        pubkey: account.key(),
+        is_signer: false,
+        is_writable: account.is_writable,
+    }).collect();
+    let mut infos: Vec<AccountInfo<'info>> = remaining.to_vec();
+    for account in changed {
+        if !infos.iter().any(|info| info.key == account.key) {
+            metas.push(AccountMeta::new_readonly(account.key(), false));
+            infos.push(account.clone());
+        }
+    }
+    Ok((metas, infos))
+}
+
+/// Withdraw fee: the destination receives the withdrawn lamports minus the fee.
+pub fn withdraw_payout(withdraw_lamports: u64, fee: u64) -> Result<u64> {
+    if fee > 0 && fee >= withdraw_lamports {
+        return Err(errors::ErrorCode::FeeExceedsAmount.into())
+    }
+    checked_sub(withdraw_lamports, fee)
+}
+
+/// Withdraw flow of withdraw, withdraw_to_new, withdraw_to_allowlisted and withdraw_and_invoke:
+/// planet -> destination (payout) and galaxy (fee, converted with the oracle in USD mode).
+/// The rent floor of the planet is the only balance check. Returns the payout.
+pub fn withdraw_comet<'info>(
+    universe: &mut Account<'info, Universe>,
+    from_planet: &mut Account<'info, Planet>,
+    destination: &AccountInfo<'info>,
+    oracle: Option<&UncheckedAccount<'info>>,
+    withdraw_lamports: u64,
+) -> Result<u64> {
+    let oracle: Option<AccountInfo> = oracle.map(|oracle| oracle.to_account_info());
+    let fee: u64 = fee_lamports(universe.wfe, universe, oracle.as_ref())?;
+    let payout: u64 = withdraw_payout(withdraw_lamports, fee)?;
+
+    // TRANSACTION - Payout to destination, fee to galaxy (both out of the withdrawn lamports)
+    assert_rent_floor(&from_planet.to_account_info(), withdraw_lamports)?;
+    from_planet.sub_lamports(withdraw_lamports)?;
+    destination.add_lamports(payout)?;
+    keep_fee(universe, fee)?;
+
+    //Universe ledger
+    record_withdraw(universe, payout)?;
+    Ok(payout)
+}
+
+/// Credits a fee (already taken from the withdrawn lamports) to the universe and records it in the ledger.
+pub fn keep_fee(universe: &mut Account<Universe>, fee: u64) -> Result<()> {
+    if fee == 0 {
+        return Ok(())
     }
-}
\ No newline at end of file
+    universe.add_lamports(fee)?;
+    record_fee(universe, fee)
+}
+
+/// Split percent for the random star splits, read from the clock.
+pub fn get_random_percent() -> Result<u64> {
+    Ok(split_percent(Clock::get()?.unix_timestamp))
+}
+
+/// Last two digits of the timestamp, clamped to 10..=90.
+pub fn split_percent(unix_timestamp: i64) -> u64 {
+    (unix_timestamp.rem_euclid(100) as u64).clamp(10, 90)
+}
+
+/// Checked `amount * numerator / denominator` computed in u128 so large deposits keep full precision.
+pub fn mul_div(amount: u64, numerator: u64, denominator: u64) -> Result<u64> {
+    let result: u128 = (amount as u128)
+        .checked_mul(numerator as u128)
+        .and_then(|product| product.checked_div(denominator as u128))
+        .ok_or(errors::ErrorCode::MathOverflow)?;
+    Ok(u64::try_from(result).map_err(|_| errors::ErrorCode::MathOverflow)?)
+}
+
+/// Checked sum of lamport amounts.
+pub fn checked_sum(amounts: &[u64]) -> Result<u64> {
+    Ok(amounts
+        .iter()
+        .try_fold(0u64, |total, amount| total.checked_add(*amount))
+        .ok_or(errors::ErrorCode::MathOverflow)?)
+}
+
+/// Checked `a - b` for lamport amounts.
+pub fn checked_sub(a: u64, b: u64) -> Result<u64> {
+    Ok(a.checked_sub(b).ok_or(errors::ErrorCode::MathOverflow)?)
+}
+
+/// Checked `id + offset` for universe star counter ids.
+pub fn star_id_offset(star_id: u64, offset: u64) -> Result<u64> {
+    Ok(star_id.checked_add(offset).ok_or(errors::ErrorCode::MathOverflow)?)
+}
+
+/// Increments planet visits without overflowing.
+pub fn add_visit(planet: &mut Planet) -> Result<()> {
+    planet.visits = planet.visits.checked_add(1).ok_or(errors::ErrorCode::MathOverflow)?;
+    Ok(())
+}
+
+/// Splits a deposit between two stars (percent from `get_random_percent`).
+pub fn split_deposit_two(deposit: u64, percent: u64) -> Result<(u64, u64)> {
+    let star_one_amount: u64 = mul_div(deposit, percent, 100)?;
+    let star_two_amount: u64 = checked_sub(deposit, star_one_amount)?;
+
+    //Make sure the addition of split amounts are equal to deposit
+    if checked_sum(&[star_one_amount, star_two_amount])? != deposit {
+        return Err(errors::ErrorCode::StarHopCalculationError.into())
+    }
+    Ok((star_one_amount, star_two_amount))
+}
+
+/// Splits a deposit between three stars.
+/// The deposit is split in two sides and the larger side is split again.
+pub fn split_deposit_three(deposit: u64, first_split_percent: u64) -> Result<(u64, u64, u64)> {
+    let second_split_percent: u64 = checked_sub(100, first_split_percent)?;
+
+    //Determine side amounts here
+    let side_one_amount: u64 = mul_div(deposit, first_split_percent, 100)?;
+    let side_two_amount: u64 = checked_sub(deposit, side_one_amount)?;
+
+    let (star_one_amount, star_two_amount, star_three_amount) = if side_one_amount > side_two_amount {
+        //Side one is larger so we split side one.
+        //Side two is set as star 2
+        //We further split side one to create star one and star three
+        let one: u64 = mul_div(side_one_amount, second_split_percent, 100)?;
+        let three: u64 = checked_sub(side_one_amount, one)?;
+        (one,side_two_amount,three)
+    } else {
+        //Side two is larger so we split side two.
+        //Side one is set as star one
+        //We further split side two to create side two and side three
+        let two: u64 = mul_div(side_two_amount, second_split_percent, 100)?;
+        let three: u64 = checked_sub(side_two_amount, two)?;
+        (side_one_amount,two,three)
+    };
+
+    if checked_sum(&[star_one_amount, star_two_amount, star_three_amount])? != deposit {
+        return Err(errors::ErrorCode::StarHopCalculationError.into())
+    }
+    Ok((star_one_amount, star_two_amount, star_three_amount))
+}
+
+/// Splits a deposit using manager supplied weights (basis points).
+/// Each weight must respect the universe min/max share policy and all weights must add up to 10000.
+/// The rounding remainder goes to the last star.
+pub fn split_deposit_weighted(deposit: u64, weights: &[u16], stars: usize, universe: &Universe) -> Result<Vec<u64>> {
+    if stars == 0 || weights.len() != stars {
+        return Err(errors::ErrorCode::InvalidSplitWeights.into())
+    }
+    if weights.iter().any(|weight| *weight < universe.smn || *weight > universe.smx) {
+        return Err(errors::ErrorCode::InvalidSplitWeights.into())
+    }
+    if weights.iter().map(|weight| *weight as u32).sum::<u32>() != BPS_TOTAL as u32 {
+        return Err(errors::ErrorCode::InvalidSplitWeights.into())
+    }
+
+    let mut amounts: Vec<u64> = weights[..stars - 1]
+        .iter()
+        .map(|weight| mul_div(deposit, *weight as u64, BPS_TOTAL as u64))
+        .collect::<Result<Vec<u64>>>()?;
+    let assigned: u64 = checked_sum(&amounts)?;
+    amounts.push(checked_sub(deposit, assigned)?);
+
+    if checked_sum(&amounts)? != deposit {
+        return Err(errors::ErrorCode::StarHopCalculationError.into())
+    }
+    Ok(amounts)
+}
+
+/// Sets a freshly created star up to hold hop funds.
+pub fn light_star(star: &mut Star, id: u64, amount: u64, manager: Pubkey, origin: Pubkey, created: i64) {
+    star.id = id;
+    star.amount = amount;
+    star.manager = manager;
+    star.created = created;
+    star.origin = origin;
+    star.in_use = true;
+}
+
+/// Rounds an amount to the nearest stats bucket (0 = no bucketing).
+/// Applied to the running totals (not to each deposit) so the stats never drift
+/// from the exact ledger by more than half a bucket. Rounds reported numbers only,
+/// deposit sizes stay visible in the exact ledger.
+pub fn bucket_amount(lamports: u64, bucket: u64) -> Result<u64> {
+    if bucket == 0 {
+        return Ok(lamports)
+    }
+    let buckets: u64 = checked_sum(&[lamports, bucket / 2])? / bucket;
+    Ok(buckets.checked_mul(bucket).ok_or(errors::ErrorCode::MathOverflow)?)
+}
+
+/// Universe ledger: records a deposit (exact ledger and bucketed stats).
+pub fn record_deposit(universe: &mut Universe, lamports: u64) -> Result<()> {
+    universe.td = checked_sum(&[universe.td, lamports])?;
+    universe.sd = bucket_amount(universe.td, universe.bk)?;
+    Ok(())
+}
+
+/// Universe ledger: records a withdrawal (exact ledger and bucketed stats).
+pub fn record_withdraw(universe: &mut Universe, lamports: u64) -> Result<()> {
+    universe.tw = checked_sum(&[universe.tw, lamports])?;
+    universe.sw = bucket_amount(universe.tw, universe.bk)?;
+    Ok(())
+}
+
+/// Universe ledger: records a collected fee (held by the universe).
+pub fn record_fee(universe: &mut Universe, lamports: u64) -> Result<()> {
+    universe.tf = checked_sum(&[universe.tf, lamports])?;
+    Ok(())
+}
+
+/// Universe ledger: token units withheld by a Token-2022 transfer fee between two vaults.
+pub fn record_transfer_fee(universe: &mut Universe, sent: u64, received: u64) -> Result<()> {
+    universe.tl = checked_sum(&[universe.tl, checked_sub(sent, received)?])?;
+    Ok(())
+}
+
+/// Universe ledger: a token withdraw fee sent from a planet vault to the treasury vault.
+/// The fee is what the treasury vault received (tf bounds the sweep), the part withheld by a
+/// Token-2022 transfer fee on the way is recorded in tl.
+pub fn record_token_withdraw_fee(universe: &mut Universe, sent: u64, received: u64) -> Result<()> {
+    record_fee(universe, received)?;
+    record_transfer_fee(universe, sent, received)
+}
+
+/// Universe stats: counts a planet hop.
+pub fn record_planet_hop(universe: &mut Universe) -> Result<()> {
+    universe.hp = checked_sum(&[universe.hp, 1])?;
+    Ok(())
+}
+
+/// Universe stats: counts a two or three star hop.
+pub fn record_star_hop(universe: &mut Universe, stars: u64) -> Result<()> {
+    if stars == 2 {
+        universe.hs2 = checked_sum(&[universe.hs2, 1])?;
+    } else {
+        universe.hs3 = checked_sum(&[universe.hs3, 1])?;
+    }
+    Ok(())
+}
+
+/// Universe ledger: stars that now hold hop funds.
+pub fn open_stars(universe: &mut Universe, count: u64) -> Result<()> {
+    universe.ns = checked_sum(&[universe.ns, count])?;
+    Ok(())
+}
+
+/// Universe ledger: stars that no longer hold hop funds.
+pub fn close_stars(universe: &mut Universe, count: u64) -> Result<()> {
+    universe.ns = checked_sub(universe.ns, count)?;
+    Ok(())
+}
+
+/// Token mode: rejects mints with extensions that would break conservation
+/// (tokens moved or frozen outside the program, or transfers that need extra accounts).
+/// Transfer fees are supported, amounts are read back from the vaults.
+pub fn assert_supported_mint(mint: &InterfaceAccount<Mint>) -> Result<()> {
+    let mint_info: AccountInfo = mint.to_account_info();
+    if *mint_info.owner != spl_token_2022::ID {
+        return Ok(())
+    }
+    let data = mint_info.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    for extension in state.get_extension_types()? {
+        if UNSUPPORTED_MINT_EXTENSIONS.contains(&extension) {
+            msg!("Unsupported mint extension: {:?}", extension);
+            return Err(errors::ErrorCode::UnsupportedMintExtension.into())
+        }
+    }
+    Ok(())
+}
+
+/// Staking: splits the whole stake account balance into (returned principal, rewards, shortfall, rent refund).
+/// The principal is clamped to what came back above rent, the rest of the principal is the shortfall.
+pub fn split_stake_withdraw(stake_lamports: u64, stake_rent: u64, principal: u64) -> Result<(u64, u64, u64, u64)> {
+    let available: u64 = stake_lamports.saturating_sub(stake_rent);
+    let returned: u64 = principal.min(available);
+    Ok((
+        returned,
+        checked_sub(available, returned)?,
+        checked_sub(principal, returned)?,
+        checked_sub(stake_lamports, available)?,
+    ))
+}
+
+/// Token mode: moves tokens with `transfer_checked`, signed by the source owner (PDA seeds or user).
+/// Returns the amount the destination actually received (Token-2022 transfer fees are withheld).
+pub fn transfer_tokens<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    from: &InterfaceAccount<'info, TokenAccount>,
+    to: &mut InterfaceAccount<'info, TokenAccount>,
+    authority: AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<u64> {
+    let balance_before: u64 = to.amount;
+    let accounts = TransferChecked {
+        from: from.to_account_info(),
+        mint: mint.to_account_info(),
+        to: to.to_account_info(),
+        authority,
+    };
+    token_interface::transfer_checked(
+        CpiContext::new_with_signer(token_program.to_account_info(), accounts, signer_seeds),
+        amount,
+        mint.decimals,
+    )?;
+    to.reload()?;
+    checked_sub(to.amount, balance_before)
+}
+
+/// Token mode: amount held by the vault of a planet or star, which must be its associated token account.
+pub fn token_vault_amount(vault: &AccountInfo, owner: &Pubkey, mint: &AccountInfo) -> Result<u64> {
+    if *vault.key != get_associated_token_address_with_program_id(owner, mint.key, mint.owner) || vault.owner != mint.owner {
+        return Err(errors::ErrorCode::InvariantVaultInvalid.into())
+    }
+    let data = vault.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+    Ok(state.base.amount)
+}
+
+/// Token mode: a planet of a token universe is only deleted once its vault is empty,
+/// otherwise the tokens would be left in a vault no planet can move.
+/// A vault that was never created counts as empty.
+pub fn assert_planet_vault_empty(universe: &Universe, planet: &AccountInfo, mint: Option<&AccountInfo>, vault: Option<&AccountInfo>) -> Result<()> {
+    if universe.mt == NATIVE_MINT_SENTINEL {
+        return Ok(())
+    }
+    let (Some(mint), Some(vault)) = (mint, vault) else {
+        return Err(errors::ErrorCode::PlanetVaultRequired.into())
+    };
+    if vault.data_is_empty() && *vault.key == get_associated_token_address_with_program_id(planet.key, mint.key, mint.owner) {
+        return Ok(())
+    }
+    if token_vault_amount(vault, planet.key, mint)? > 0 {
+        return Err(errors::ErrorCode::PlanetVaultNotEmpty.into())
+    }
+    Ok(())
+}
+
+/// wSOL universe: moves native lamports from a signer into a wSOL token account and syncs its amount.
+pub fn wrap_lamports<'info>(
+    from: &Signer<'info>,
+    to: &InterfaceAccount<'info, TokenAccount>,
+    system: &Program<'info, System>,
+    token_program: &Interface<'info, TokenInterface>,
+    lamports: u64,
+) -> Result<()> {
+    let transfer_accounts = system_program::Transfer {
+        from: from.to_account_info(),
+        to: to.to_account_info(),
+    };
+    system_program::transfer(CpiContext::new(system.to_account_info(), transfer_accounts), lamports)?;
+    let sync_accounts = SyncNative {
+        account: to.to_account_info(),
+    };
+    token_interface::sync_native(CpiContext::new(token_program.to_account_info(), sync_accounts))
+}
+
+/// Token-2022 vaults of a transfer fee mint cannot close while they hold withheld fees.
+/// Mints without `TransferFeeConfig` (including plain Token-2022 mints) never need a harvest.
+pub fn has_withheld_fees(mint_data: &[u8], vault_data: &[u8]) -> Result<bool> {
+    let mint = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(mint_data)?;
+    if mint.get_extension::<TransferFeeConfig>().is_err() {
+        return Ok(false)
+    }
+    let vault = StateWithExtensions::<spl_token_2022::state::Account>::unpack(vault_data)?;
+    Ok(vault.get_extension::<TransferFeeAmount>().is_ok_and(|fee| u64::from(fee.withheld_amount) > 0))
+}
+
+/// Token mode: closes an emptied star vault, the vault rent goes to the manager.
+/// Withheld Token-2022 transfer fees are harvested to the mint first (see `has_withheld_fees`).
+pub fn close_star_vault<'info>(
+    token_program: &Interface<'info, TokenInterface>,
+    mint: &InterfaceAccount<'info, Mint>,
+    vault: &InterfaceAccount<'info, TokenAccount>,
+    star: AccountInfo<'info>,
+    manager: AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let needs_harvest: bool = token_program.key() == spl_token_2022::ID
+        && has_withheld_fees(&mint.to_account_info().try_borrow_data()?, &vault.to_account_info().try_borrow_data()?)?;
+    if needs_harvest {
+        let accounts = HarvestWithheldTokensToMint {
+            token_program_id: token_program.to_account_info(),
+            mint: mint.to_account_info(),
+        };
+        token_interface::harvest_withheld_tokens_to_mint(
+            CpiContext::new(token_program.to_account_info(), accounts),
+            vec![vault.to_account_info()],
+        )?;
+    }
+    let accounts = CloseAccount {
+        account: vault.to_account_info(),
+        destination: manager,
+        authority: star,
+    };
+    token_interface::close_account(CpiContext::new_with_signer(token_program.to_account_info(), accounts, signer_seeds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_spl::token_2022::spl_token_2022::extension::{BaseStateWithExtensionsMut, ExtensionType, StateWithExtensionsMut};
+    use anchor_spl::token_2022::spl_token_2022::state::{Account, AccountState};
+
+    fn overflow() -> Error {
+        errors::ErrorCode::MathOverflow.into()
+    }
+
+    fn token_2022_mint(extensions: &[ExtensionType]) -> Vec<u8> {
+        let len = ExtensionType::try_calculate_account_len::<spl_token_2022::state::Mint>(extensions).unwrap();
+        let mut data = vec![0u8; len];
+        let mut state = StateWithExtensionsMut::<spl_token_2022::state::Mint>::unpack_uninitialized(&mut data).unwrap();
+        if extensions.contains(&ExtensionType::TransferFeeConfig) {
+            state.init_extension::<TransferFeeConfig>(true).unwrap();
+        }
+        state.base.decimals = 6;
+        state.base.is_initialized = true;
+        state.pack_base();
+        if !extensions.is_empty() {
+            state.init_account_type().unwrap();
+        }
+        data
+    }
+
+    fn token_2022_vault(withheld: Option<u64>) -> Vec<u8> {
+        let extensions: Vec<ExtensionType> = withheld.iter().map(|_| ExtensionType::TransferFeeAmount).collect();
+        let len = ExtensionType::try_calculate_account_len::<Account>(&extensions).unwrap();
+        let mut data = vec![0u8; len];
+        let mut state = StateWithExtensionsMut::<Account>::unpack_uninitialized(&mut data).unwrap();
+        if let Some(withheld) = withheld {
+            state.init_extension::<TransferFeeAmount>(true).unwrap().withheld_amount = withheld.into();
+        }
+        state.base.state = AccountState::Initialized;
+        state.pack_base();
+        if withheld.is_some() {
+            state.init_account_type().unwrap();
+        }
+        data
+    }
+
+    #[test]
+    fn mul_div_keeps_precision_at_u64_max() {
+        assert_eq!(mul_div(u64::MAX, 90, 100).unwrap(), 16602069666338596453);
+        assert_eq!(mul_div(u64::MAX, 10_000, 10_000).unwrap(), u64::MAX);
+        assert_eq!(mul_div(u64::MAX, u64::MAX, u64::MAX).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn mul_div_rejects_overflow_and_zero_denominator() {
+        assert_eq!(mul_div(u64::MAX, 2, 1).unwrap_err(), overflow());
+        assert_eq!(mul_div(1, 1, 0).unwrap_err(), overflow());
+    }
+
+    #[test]
+    fn checked_sum_and_sub_at_boundaries() {
+        assert_eq!(checked_sum(&[u64::MAX - 1, 1]).unwrap(), u64::MAX);
+        assert_eq!(checked_sum(&[u64::MAX, 1]).unwrap_err(), overflow());
+        assert_eq!(checked_sum(&[]).unwrap(), 0);
+        assert_eq!(checked_sub(u64::MAX, u64::MAX).unwrap(), 0);
+        assert_eq!(checked_sub(0, 1).unwrap_err(), overflow());
+    }
+
+    #[test]
+    fn star_id_offset_rejects_exhausted_counter() {
+        assert_eq!(star_id_offset(u64::MAX - 2, 2).unwrap(), u64::MAX);
+        assert_eq!(star_id_offset(u64::MAX, 1).unwrap_err(), overflow());
+    }
+
+    #[test]
+    fn bucket_amount_rounds_to_nearest_bucket() {
+        assert_eq!(bucket_amount(1_234, 0).unwrap(), 1_234);
+        assert_eq!(bucket_amount(1_499, 1_000).unwrap(), 1_000);
+        assert_eq!(bucket_amount(1_500, 1_000).unwrap(), 2_000);
+        assert_eq!(bucket_amount(u64::MAX, 1_000).unwrap_err(), overflow());
+    }
+
+    #[test]
+    fn bucketed_running_total_does_not_drift() {
+        //Many deposits below half a bucket still show up in the bucketed total
+        let mut total: u64 = 0;
+        for _ in 0..1_000 {
+            total = checked_sum(&[total, 400]).unwrap();
+        }
+        assert_eq!(bucket_amount(total, 1_000).unwrap(), 400_000);
+    }
+
+    #[test]
+    fn split_percent_is_clamped() {
+        assert_eq!(split_percent(1_700_000_005), 10);
+        assert_eq!(split_percent(1_700_000_042), 42);
+        assert_eq!(split_percent(1_700_000_099), 90);
+        assert_eq!(split_percent(-1), 90);
+    }
+
+    #[test]
+    fn split_two_adds_up_at_u64_max() {
+        for percent in [10, 42, 90] {
+            let (one, two) = split_deposit_two(u64::MAX, percent).unwrap();
+            assert_eq!(one as u128 + two as u128, u64::MAX as u128);
+        }
+        assert_eq!(split_deposit_two(1, 50).unwrap(), (0, 1));
+        assert_eq!(split_deposit_two(0, 50).unwrap(), (0, 0));
+    }
+
+    #[test]
+    fn split_three_adds_up_at_u64_max() {
+        for percent in [10, 42, 50, 90] {
+            let (one, two, three) = split_deposit_three(u64::MAX, percent).unwrap();
+            assert_eq!(one as u128 + two as u128 + three as u128, u64::MAX as u128);
+        }
+        let (one, two, three) = split_deposit_three(2, 50).unwrap();
+        assert_eq!(one + two + three, 2);
+    }
+
+    #[test]
+    fn split_three_rejects_invalid_percent() {
+        assert_eq!(split_deposit_three(100, 101).unwrap_err(), overflow());
+    }
+
+    #[test]
+    fn plain_token_2022_mint_never_harvests() {
+        let mint = token_2022_mint(&[]);
+        assert!(!has_withheld_fees(&mint, &token_2022_vault(None)).unwrap());
+    }
+
+    #[test]
+    fn transfer_fee_mint_harvests_only_withheld_fees() {
+        let mint = token_2022_mint(&[ExtensionType::TransferFeeConfig]);
+        assert!(!has_withheld_fees(&mint, &token_2022_vault(Some(0))).unwrap());
+        assert!(has_withheld_fees(&mint, &token_2022_vault(Some(7))).unwrap());
+    }
+
+    #[test]
+    fn stake_withdraw_splits_rewards_and_shortfall() {
+        //Rewards
+        assert_eq!(split_stake_withdraw(2_282_880 + 1_000_005, 2_282_880, 1_000_000).unwrap(), (1_000_000, 5, 0, 2_282_880));
+        //Short principal (never stuck)
+        assert_eq!(split_stake_withdraw(2_282_880 + 990_000, 2_282_880, 1_000_000).unwrap(), (990_000, 0, 10_000, 2_282_880));
+        //Short rent
+        assert_eq!(split_stake_withdraw(1_000, 2_282_880, 1_000_000).unwrap(), (0, 0, 1_000_000, 1_000));
+        assert_eq!(split_stake_withdraw(u64::MAX, 0, u64::MAX).unwrap(), (u64::MAX, 0, 0, 0));
+    }
+
+    #[test]
+    fn withdraw_fee_comes_out_of_the_withdrawn_lamports() {
+        assert_eq!(withdraw_payout(100, 0).unwrap(), 100);
+        assert_eq!(withdraw_payout(100, 1).unwrap(), 99);
+        assert_eq!(withdraw_payout(u64::MAX, u64::MAX - 1).unwrap(), 1);
+        assert!(withdraw_payout(100, 100).is_err());
+        assert!(withdraw_payout(0, 0).is_ok());
+    }
+
+    #[test]
+    fn invoke_accounts_include_every_changed_account() {
+        let keys: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+        let (destination, other, planet, universe) = (keys[0], keys[1], keys[2], keys[3]);
+        let owner: Pubkey = Pubkey::default();
+        let mut lamports: Vec<u64> = vec![1; 4];
+        let mut data: Vec<Vec<u8>> = vec![Vec::new(); 4];
+        let infos: Vec<AccountInfo> = keys.iter().zip(lamports.iter_mut()).zip(data.iter_mut())
+            .map(|((key, lamports), data)| AccountInfo::new(key, false, true, lamports, data, &owner, false, 0))
+            .collect();
+
+        //Destination read by the callback, planet and universe appended read-only
+        let (metas, called) = invoke_accounts(&infos[..2], &destination, &infos[2..]).unwrap();
+        let keys_of = |metas: &[AccountMeta]| metas.iter().map(|meta| meta.pubkey).collect::<Vec<Pubkey>>();
+        assert_eq!(keys_of(&metas), vec![destination, other, planet, universe]);
+        assert_eq!(called.len(), 4);
+        assert!(metas[0].is_writable && !metas[2].is_writable && !metas[3].is_writable);
+
+        //Planet passed by the caller is not repeated
+        let (metas, _) = invoke_accounts(&infos[..3], &destination, &infos[2..]).unwrap();
+        assert_eq!(keys_of(&metas), vec![destination, other, planet, universe]);
+
+        //The callback must see the destination
+        assert!(invoke_accounts(&infos[1..2], &destination, &infos[2..]).is_err());
+        let mut signer: AccountInfo = infos[0].clone();
+        signer.is_signer = true;
+        assert!(invoke_accounts(&[signer], &destination, &infos[2..]).is_err());
+    }
+
+    #[test]
+    fn token_planets_are_deleted_with_an_empty_vault_only() {
+        let mut data: Vec<u8> = Universe::DISCRIMINATOR.to_vec();
+        data.resize(Universe::LEN, 0);
+        let mut universe: Universe = Universe::try_deserialize(&mut &data[..]).unwrap();
+        let (planet_key, mint_key, system) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::default());
+        let vault_key: Pubkey = get_associated_token_address_with_program_id(&planet_key, &mint_key, &spl_token_2022::ID);
+        let (mut planet_lamports, mut mint_lamports, mut vault_lamports) = (1, 1, 1);
+        let (mut planet_data, mut mint_data, mut empty_data) = (Vec::new(), token_2022_mint(&[]), Vec::new());
+        let mut vault_data: Vec<u8> = token_2022_vault(None);
+        let planet = AccountInfo::new(&planet_key, false, true, &mut planet_lamports, &mut planet_data, &system, false, 0);
+        let mint = AccountInfo::new(&mint_key, false, false, &mut mint_lamports, &mut mint_data, &spl_token_2022::ID, false, 0);
+
+        //SOL universes have no vault
+        assert!(assert_planet_vault_empty(&universe, &planet, None, None).is_ok());
+        universe.mt = mint_key;
+        assert!(assert_planet_vault_empty(&universe, &planet, None, None).is_err());
+
+        //Vault never created
+        let mut uncreated_lamports: u64 = 0;
+        let uncreated = AccountInfo::new(&vault_key, false, false, &mut uncreated_lamports, &mut empty_data, &system, false, 0);
+        assert!(assert_planet_vault_empty(&universe, &planet, Some(&mint), Some(&uncreated)).is_ok());
+
+        //Empty vault, then a vault holding tokens
+        {
+            let vault = AccountInfo::new(&vault_key, false, false, &mut vault_lamports, &mut vault_data, &spl_token_2022::ID, false, 0);
+            assert!(assert_planet_vault_empty(&universe, &planet, Some(&mint), Some(&vault)).is_ok());
+        }
+        let mut state = StateWithExtensionsMut::<Account>::unpack(&mut vault_data).unwrap();
+        state.base.amount = 1;
+        state.pack_base();
+        let vault = AccountInfo::new(&vault_key, false, false, &mut vault_lamports, &mut vault_data, &spl_token_2022::ID, false, 0);
+        assert!(assert_planet_vault_empty(&universe, &planet, Some(&mint), Some(&vault)).is_err());
+    }
+
+    #[test]
+    fn hop_credits_run_out_at_zero() {
+        let mut comet: Comet = Comet { universe: Pubkey::default(), creator: Pubkey::default(), id: 0, ph: 0, s2: 0, s3: 0, bump: 0 };
+        open_comet(&mut comet, Pubkey::new_unique(), Pubkey::new_unique(), &HopPackage { id: 7, planet: 1, star_two: 0, star_three: 2 }, 255);
+        assert_eq!((comet.id, comet.ph, comet.s2, comet.s3), (7, 1, 0, 2));
+        consume_hop_credit(&mut comet.ph).unwrap();
+        assert_eq!(comet.ph, 0);
+        assert!(consume_hop_credit(&mut comet.ph).is_err());
+        assert!(consume_hop_credit(&mut comet.s2).is_err());
+        consume_hop_credit(&mut comet.s3).unwrap();
+        assert_eq!(comet.s3, 1);
+    }
+
+    #[test]
+    fn denominations_are_positive_increasing_and_bounded() {
+        assert!(assert_valid_denominations(&[]).is_ok());
+        assert!(assert_valid_denominations(&[1_000, 10_000, 100_000]).is_ok());
+        assert!(assert_valid_denominations(&[0, 10_000]).is_err());
+        assert!(assert_valid_denominations(&[10_000, 10_000]).is_err());
+        assert!(assert_valid_denominations(&[10_000, 1_000]).is_err());
+        let too_many: Vec<u64> = (1..=MAX_DENOMINATIONS as u64 + 1).collect();
+        assert!(assert_valid_denominations(&too_many).is_err());
+    }
+}
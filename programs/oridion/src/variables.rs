@@ -1,27 +1,85 @@
 use solana_program::{pubkey, pubkey::Pubkey};
+use anchor_spl::token_2022::spl_token_2022::extension::ExtensionType;
 
+#[cfg(not(feature = "localnet"))]
 pub const MANAGER_PUBKEY: Pubkey = pubkey!("ordnd8TZFYW4k4MeLrR3qSwXMxezL6W3WryUPYTzLQM");
+#[cfg(feature = "localnet")]
+pub const MANAGER_PUBKEY: Pubkey = pubkey!("E8NkboC2xnZiBuDSq6rybpSd79zMvTPEJc3wVnJbX1up"); //tests/keys/manager.json
 pub const UNIVERSE_PDA_SEED: &[u8] = b"_x_ORIDION_x_";
+pub const NATIVE_MINT_SENTINEL: Pubkey = Pubkey::new_from_array([0; 32]); //Universe mint for native SOL
 
 //PLANET
 pub const PLANET_PDA_SEED_PRE: &[u8] = b"_PLA_";
 pub const PLANET_PDA_SEED_POST: &[u8] = b"_NET_";
+pub const PLANET_NAME_MAX_CHARS: usize = PLANET_NAME; //Canonical names are uppercase A-Z / 0-9 (1 byte each)
 
 //Constants for Star seed
 pub const STAR_SEED_PRE: &[u8] = b"_ST_";
 pub const STAR_SEED_POST: &[u8] = b"_AR_";
+pub const STAR_POOL_SEED_PRE: &[u8] = b"_PO_ST_";
+
+//Constants for the comet (prepaid hop credits)
+pub const COMET_SEED: &[u8] = b"_CO_MET_";
+
+//Constants for the temporary wSOL account used to unwrap withdrawals
+pub const WSOL_UNWRAP_SEED: &[u8] = b"_UN_WRAP_";
+pub const DEFAULT_STAR_TIMEOUT: i64 = 60 * 60; //Seconds before a stranded star can be recovered
+
+//Constants for the withdraw destination allowlist
+pub const ALLOWLIST_SEED: &[u8] = b"_ALLOW_LIST_";
+pub const MAX_ALLOWLIST_DESTINATIONS: usize = 16;
+pub const MAX_ALLOWLIST_PROGRAMS: usize = 8;
+
+//Deposit denominations (fixed deposit amounts, empty = any amount)
+pub const MAX_DENOMINATIONS: usize = 8;
+
+//Constants for native staking of planet lamports
+pub const STAKE_SEED: &[u8] = b"_ST_AKE_";
+pub const STAKE_CONFIG_ID: Pubkey = pubkey!("StakeConfig11111111111111111111111111111111");
+
+//USD fees (micro-cents) converted with a Pyth v2 compatible SOL/USD price account
+pub const MICRO_CENTS_PER_USD: u64 = 100_000_000;
+pub const PYTH_MAGIC: u32 = 0xa1b2c3d4;
+pub const PYTH_VERSION: u32 = 2;
+pub const PYTH_ACCOUNT_TYPE_PRICE: u32 = 3;
+pub const PYTH_STATUS_TRADING: u32 = 1;
+pub const PYTH_MAX_EXPONENT: u32 = 18; //|expo| beyond this is rejected
+pub const PYTH_EXPO_OFFSET: usize = 20; //i32
+pub const PYTH_TIMESTAMP_OFFSET: usize = 96; //i64 unix timestamp of the last update
+pub const PYTH_PRICE_OFFSET: usize = 208; //i64 aggregate price
+pub const PYTH_CONF_OFFSET: usize = 216; //u64 aggregate confidence
+pub const PYTH_STATUS_OFFSET: usize = 224; //u32 aggregate status
+pub const PYTH_MIN_ACCOUNT_LENGTH: usize = 240;
+
+//Star split shares in basis points
+pub const BPS_TOTAL: u16 = 10000;
+pub const DEFAULT_MIN_SHARE_BPS: u16 = 1000; //10% (same floor as the random split)
+pub const DEFAULT_MAX_SHARE_BPS: u16 = 9000; //90% (same ceiling as the random split)
+
+//Token-2022 mint extensions rejected in token mode
+pub const UNSUPPORTED_MINT_EXTENSIONS: [ExtensionType; 5] = [
+    ExtensionType::PermanentDelegate, //Delegate can move vault tokens
+    ExtensionType::NonTransferable,
+    ExtensionType::DefaultAccountState, //Vaults could start frozen
+    ExtensionType::TransferHook, //Transfers need extra accounts
+    ExtensionType::ConfidentialTransferMint, //Balances leave the public amount
+];
 
 /// Constants for sizing properties.
 pub  const DISCRIMINATOR_LENGTH: usize = 8;
 pub const PUBLIC_KEY_LENGTH: usize = 32;
-pub const STAR_ID_LENGTH: usize = 8 * 4; // 8 chars max.
-pub const PLANET_NAME: usize = 10 * 4; //10 characters long
+pub const STAR_ID_LENGTH: usize = 8; // u64 star counter
+pub const PLANET_NAME: usize = 10 * 4; //40 bytes (seed is hashed so names can exceed 32 bytes)
 pub const TIMESTAMP_LENGTH: usize = 8;
 pub const STRING_LENGTH_PREFIX: usize = 4; // Stores the size of the string.
 pub const LAMPORT_LENGTH: usize = 8; //u64 = 8 bytes
 
 pub const U8_LENGTH: usize = 1; //BUMP = u8 1 = byte
 pub const U64_LENGTH: usize = 8; //8 = bytes
+pub const U32_LENGTH: usize = 4; //4 = bytes
+pub const U16_LENGTH: usize = 2; //2 = bytes
+pub const BOOL_LENGTH: usize = 1; //bool = 1 byte
 
 //Planet count length
-pub const PLANETS_VEC_LENGTH: usize = 104; // 4 + ((4 + 6) * 10)
\ No newline at end of file
+pub const MAX_PLANETS: usize = 10;
+pub const PLANETS_VEC_LENGTH: usize = STRING_LENGTH_PREFIX + (STRING_LENGTH_PREFIX + PLANET_NAME) * MAX_PLANETS; // 4 + ((4 + 40) * 10)
\ No newline at end of file
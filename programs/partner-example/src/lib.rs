@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use oridion::cpi::accounts::CreateComet;
+use oridion::program::Oridion;
+
+declare_id!("2mV3494ZKkGyH6QCA6i5YyML3kVrzGGuPKuTxXDmGdqQ");
+
+pub const VAULT_SEED: &[u8] = b"_PARTNER_VAULT_";
+
+///-------------------------------------------------------------------///
+/// PARTNER EXAMPLE
+/// Minimal partner program depositing into Oridion from its own PDA through CPI.
+/// The vault PDA is system owned (no data) and funded beforehand.
+///-------------------------------------------------------------------///
+#[program]
+pub mod partner_example {
+    use super::*;
+
+    pub fn deposit(ctx: Context<Deposit>, deposit_lamports: u64) -> Result<()> {
+        let vault_bump: [u8; 1] = [ctx.bumps.vault];
+        let signer: &[&[&[u8]]] = &[&[VAULT_SEED, &vault_bump]];
+
+        //Stable Oridion account order: creator, universe, planet, system_program, rent, oracle
+        let accounts = CreateComet {
+            creator: ctx.accounts.vault.to_account_info(),
+            universe: ctx.accounts.universe.to_account_info(),
+            planet: ctx.accounts.planet.to_account_info(),
+            system_program: ctx.accounts.system_program.to_account_info(),
+            rent: ctx.accounts.rent.to_account_info(),
+            oracle: ctx.accounts.oracle.as_ref().map(|oracle| oracle.to_account_info()),
+        };
+        oridion::cpi::new_comet(
+            CpiContext::new_with_signer(ctx.accounts.oridion_program.to_account_info(), accounts, signer),
+            deposit_lamports,
+        )
+    }
+}
+
+#[derive(Accounts)]
+pub struct Deposit<'info> {
+    #[account(mut, seeds = [VAULT_SEED], bump)]
+    pub vault: SystemAccount<'info>,
+    /// CHECK: validated by Oridion
+    #[account(mut)]
+    pub universe: UncheckedAccount<'info>,
+    /// CHECK: validated by Oridion
+    #[account(mut)]
+    pub planet: UncheckedAccount<'info>,
+    pub oridion_program: Program<'info, Oridion>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+    /// CHECK: validated by Oridion (only needed for USD fees)
+    pub oracle: Option<UncheckedAccount<'info>>,
+}
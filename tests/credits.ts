@@ -0,0 +1,145 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { TOKEN_PROGRAM_ID, createMint, getAssociatedTokenAddressSync, getOrCreateAssociatedTokenAccount, mintTo, getAccount } from "@solana/spl-token";
+import { expect } from "chai";
+import { program, provider, manager, ensurePlanet, depositSol, fundedKeypair, balance, expectError, universePda, cometPda, nextCometId, NATIVE_MINT_SENTINEL } from "./helpers";
+
+// Prepaid hop credits: packages bought with a deposit are stored on a comet,
+// every hop start consumes one credit of its kind and is rejected when none remain.
+describe("prepaid hop credits", () => {
+    const connection = provider.connection;
+
+    describe("sol universe", () => {
+        const universe = universePda(NATIVE_MINT_SENTINEL);
+        let red: PublicKey;
+        let blue: PublicKey;
+        let user: Keypair;
+        let comet: PublicKey;
+
+        const planetHop = (comet: PublicKey, lamports: number) =>
+            program.methods.planetHop(new anchor.BN(lamports)).accountsPartial({ universe, comet, toPlanet: blue, fromPlanet: red, manager: manager.publicKey }).rpc();
+
+        before(async () => {
+            red = await ensurePlanet("RED", NATIVE_MINT_SENTINEL);
+            blue = await ensurePlanet("BLUE", NATIVE_MINT_SENTINEL);
+            user = await fundedKeypair(5);
+            await program.methods.updateFee(0, 1_000, 2_000, 3_000, 0).accountsPartial({ universe, creator: manager.publicKey }).rpc();
+        });
+
+        after(async () => {
+            await program.methods.updateFee(0, 0, 0, 0, 0).accountsPartial({ universe, creator: manager.publicKey }).rpc();
+        });
+
+        it("sells a package with new_comet_with_hops and stores its credits on the comet", async () => {
+            const id = nextCometId();
+            comet = cometPda(universe, id);
+            const before = await program.account.universe.fetch(universe);
+            const redBefore = await balance(red);
+            await program.methods
+                .newCometWithHops(new anchor.BN(100_000_000), { id: new anchor.BN(id), planet: 2, starTwo: 1, starThree: 0 })
+                .accountsPartial({ deposit: { creator: user.publicKey, universe, planet: red, oracle: null }, comet })
+                .signers([user])
+                .rpc();
+            const after = await program.account.universe.fetch(universe);
+            const fee = 2 * 1_000 + 2_000;
+            expect((await balance(red)) - redBefore).to.equal(100_000_000 - fee);
+            expect(after.tf.sub(before.tf).toNumber()).to.equal(fee);
+            const credits = await program.account.comet.fetch(comet);
+            expect([credits.ph, credits.s2, credits.s3]).to.deep.equal([2, 1, 0]);
+            expect(credits.universe.toBase58()).to.equal(universe.toBase58());
+        });
+
+        it("consumes the comet credits and rejects hops once they run out", async () => {
+            await planetHop(comet, 1_000_000);
+            await planetHop(comet, 1_000_000);
+            expect((await program.account.comet.fetch(comet)).ph).to.equal(0);
+            await expectError(planetHop(comet, 1_000_000), "NoHopCredits");
+        });
+
+        it("rejects hops for a package without credits of that kind", async () => {
+            const id = nextCometId();
+            const empty = cometPda(universe, id);
+            await program.methods
+                .newCometWithHops(new anchor.BN(10_000_000), { id: new anchor.BN(id), planet: 0, starTwo: 0, starThree: 0 })
+                .accountsPartial({ deposit: { creator: user.publicKey, universe, planet: red, oracle: null }, comet: empty })
+                .signers([user])
+                .rpc();
+            await expectError(planetHop(empty, 1_000_000), "NoHopCredits");
+        });
+
+        it("closes the comet and returns its rent to the creator", async () => {
+            const before = await balance(user.publicKey);
+            await program.methods.closeComet().accountsPartial({ comet, creator: user.publicKey }).signers([user]).rpc();
+            expect(await connection.getAccountInfo(comet, "confirmed")).to.be.null;
+            expect(await balance(user.publicKey)).to.be.greaterThan(before);
+        });
+
+        it("keeps new_comet backward compatible (no package, no extra accounts)", async () => {
+            await depositSol(red, 10_000_000, user);
+        });
+    });
+
+    describe("token universe", () => {
+        let mint: PublicKey;
+        let universe: PublicKey;
+        let green: PublicKey;
+        let white: PublicKey;
+        let user: Keypair;
+        let source: PublicKey;
+
+        const vault = (owner: PublicKey): PublicKey => getAssociatedTokenAddressSync(mint, owner, true, TOKEN_PROGRAM_ID);
+        const tokens = async (account: PublicKey): Promise<bigint> => (await getAccount(connection, account, "confirmed")).amount;
+        const tokenHop = (comet: PublicKey, amount: number) =>
+            program.methods
+                .tokenPlanetHop(new anchor.BN(amount))
+                .accountsPartial({ universe, comet, mint, toPlanet: white, toVault: vault(white), fromPlanet: green, fromVault: vault(green), manager: manager.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+                .rpc();
+
+        before(async () => {
+            user = await fundedKeypair(2);
+            mint = await createMint(connection, manager, manager.publicKey, null, 6);
+            green = await ensurePlanet("GREEN", mint);
+            white = await ensurePlanet("WHITE", mint);
+            universe = universePda(mint);
+            for (const planet of [green, white]) {
+                await program.methods.createPlanetVault().accountsPartial({ universe, planet, mint, vault: vault(planet), creator: manager.publicKey, tokenProgram: TOKEN_PROGRAM_ID }).rpc();
+            }
+            await program.methods.createTreasuryVault().accountsPartial({ universe, mint, vault: vault(universe), creator: manager.publicKey, tokenProgram: TOKEN_PROGRAM_ID }).rpc();
+            await program.methods.updateFee(0, 500, 0, 0, 0).accountsPartial({ universe, creator: manager.publicKey }).rpc();
+            source = (await getOrCreateAssociatedTokenAccount(connection, manager, mint, user.publicKey)).address;
+            await mintTo(connection, manager, mint, source, manager, 1_000_000);
+        });
+
+        it("sells a token package into the treasury vault and consumes its credit", async () => {
+            const id = nextCometId();
+            const comet = cometPda(universe, id);
+            await program.methods
+                .newTokenCometWithHops(new anchor.BN(100_000), { id: new anchor.BN(id), planet: 1, starTwo: 0, starThree: 0 })
+                .accountsPartial({
+                    deposit: { universe, creator: user.publicKey, planet: green, mint, source, planetVault: vault(green), treasuryVault: vault(universe), tokenProgram: TOKEN_PROGRAM_ID },
+                    payer: user.publicKey,
+                    comet,
+                })
+                .signers([user])
+                .rpc();
+            expect(await tokens(vault(universe))).to.equal(500n);
+            expect(await tokens(vault(green))).to.equal(100_000n - 500n);
+            expect((await program.account.comet.fetch(comet)).ph).to.equal(1);
+
+            await tokenHop(comet, 10_000);
+            expect((await program.account.comet.fetch(comet)).ph).to.equal(0);
+            await expectError(tokenHop(comet, 10_000), "NoHopCredits");
+        });
+
+        it("rejects a comet of another universe", async () => {
+            const id = nextCometId();
+            const solComet = cometPda(universePda(NATIVE_MINT_SENTINEL), id);
+            await program.methods
+                .newCometWithHops(new anchor.BN(10_000_000), { id: new anchor.BN(id), planet: 1, starTwo: 0, starThree: 0 })
+                .accountsPartial({ deposit: { creator: user.publicKey, universe: universePda(NATIVE_MINT_SENTINEL), planet: await ensurePlanet("RED", NATIVE_MINT_SENTINEL), oracle: null }, comet: solComet })
+                .signers([user])
+                .rpc();
+            await expectError(tokenHop(solComet, 10_000), "CometWrongUniverse");
+        });
+    });
+});
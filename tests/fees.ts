@@ -0,0 +1,101 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, SystemProgram, Transaction } from "@solana/web3.js";
+import { expect } from "chai";
+import { MockOracle } from "../target/types/mock_oracle";
+import { program, provider, manager, ensurePlanet, depositSol, fundedKeypair, balance, expectError, NATIVE_MINT_SENTINEL } from "./helpers";
+
+const PRICE_ACCOUNT_LENGTH = 240;
+
+// USD fees converted with the mock oracle (Pyth v2 layout), withdraw fees kept from the
+// withdrawn amount and the treasury sweep. Fees are reset to 0 afterwards (shared SOL universe).
+describe("usd fees and treasury", () => {
+    const oracleProgram = anchor.workspace.MockOracle as Program<MockOracle>;
+    const priceAccount = Keypair.generate();
+    const withdrawLamports = 100_000_000;
+    const usdFee = 100_000_000; // 1 USD in micro-cents
+    // 1 USD at 150 USD/SOL (price 150e8, expo -8): 1e8 * 1e9 * 1e8 / (150e8 * 1e8) lamports, rounded down
+    const feeLamports = 6_666_666;
+    let universe: PublicKey;
+    let planet: PublicKey;
+
+    const now = async (): Promise<number> => {
+        const slot = await provider.connection.getSlot("confirmed");
+        return (await provider.connection.getBlockTime(slot))!;
+    };
+    const setPrice = async (timestamp: number, trading = true) =>
+        oracleProgram.methods
+            .setPrice(new anchor.BN(15_000_000_000), -8, new anchor.BN(1_000_000), trading, new anchor.BN(timestamp))
+            .accounts({ priceAccount: priceAccount.publicKey, authority: manager.publicKey })
+            .rpc();
+    const withdraw = (destination: PublicKey, oracle: PublicKey | null) =>
+        program.methods
+            .withdraw(new anchor.BN(withdrawLamports))
+            .accountsPartial({ universe, fromPlanet: planet, destination, manager: manager.publicKey, oracle })
+            .rpc();
+
+    before(async () => {
+        planet = await ensurePlanet("FEES", NATIVE_MINT_SENTINEL);
+        universe = (await program.account.planet.fetch(planet)).universe;
+        await depositSol(planet, 1_000_000_000, await fundedKeypair(5));
+
+        const rent = await provider.connection.getMinimumBalanceForRentExemption(PRICE_ACCOUNT_LENGTH);
+        await provider.sendAndConfirm(
+            new Transaction().add(
+                SystemProgram.createAccount({ fromPubkey: manager.publicKey, newAccountPubkey: priceAccount.publicKey, lamports: rent, space: PRICE_ACCOUNT_LENGTH, programId: oracleProgram.programId })
+            ),
+            [priceAccount]
+        );
+        await setPrice(await now());
+        await program.methods.updateFeeOracle(true, priceAccount.publicKey, new anchor.BN(60), 100).accountsPartial({ universe, creator: manager.publicKey }).rpc();
+        await program.methods.updateFee(0, 0, 0, 0, usdFee).accountsPartial({ universe, creator: manager.publicKey }).rpc();
+    });
+
+    after(async () => {
+        await program.methods.updateFee(0, 0, 0, 0, 0).accountsPartial({ universe, creator: manager.publicKey }).rpc();
+        await program.methods.updateFeeOracle(false, PublicKey.default, new anchor.BN(0), 0).accountsPartial({ universe, creator: manager.publicKey }).rpc();
+    });
+
+    it("requires the oracle for USD fees", async () => {
+        await expectError(withdraw(Keypair.generate().publicKey, null), "FeeOracleMissing");
+    });
+
+    it("keeps the converted withdraw fee from the withdrawn lamports", async () => {
+        const destination = (await fundedKeypair(1)).publicKey;
+        const destinationBefore = await balance(destination);
+        const planetBefore = await balance(planet);
+        const ledgerBefore = await program.account.universe.fetch(universe);
+
+        await withdraw(destination, priceAccount.publicKey);
+
+        const ledgerAfter = await program.account.universe.fetch(universe);
+        expect(planetBefore - (await balance(planet))).to.equal(withdrawLamports);
+        expect((await balance(destination)) - destinationBefore).to.equal(withdrawLamports - feeLamports);
+        expect(ledgerAfter.tf.sub(ledgerBefore.tf).toNumber()).to.equal(feeLamports);
+        expect(ledgerAfter.tw.sub(ledgerBefore.tw).toNumber()).to.equal(withdrawLamports - feeLamports);
+    });
+
+    it("rejects a stale price", async () => {
+        await setPrice((await now()) - 3600);
+        await expectError(withdraw((await fundedKeypair(1)).publicKey, priceAccount.publicKey), "FeeOracleStale");
+    });
+
+    it("rejects a price that is not trading", async () => {
+        await setPrice(await now(), false);
+        await expectError(withdraw((await fundedKeypair(1)).publicKey, priceAccount.publicKey), "FeeOracleInvalidAccount");
+    });
+
+    it("sweeps collected fees out of the treasury", async () => {
+        const ledger = await program.account.universe.fetch(universe);
+        const sweepable = ledger.tf.add(ledger.rw).sub(ledger.ts);
+        const treasury = Keypair.generate().publicKey;
+
+        await expectError(
+            program.methods.sweepTreasury(sweepable.addn(1)).accountsPartial({ universe, destination: treasury, creator: manager.publicKey }).rpc(),
+            "SweepExceedsTreasury"
+        );
+        await program.methods.sweepTreasury(sweepable).accountsPartial({ universe, destination: treasury, creator: manager.publicKey }).rpc();
+        expect(await balance(treasury)).to.equal(sweepable.toNumber());
+        expect((await program.account.universe.fetch(universe)).ts.toString()).to.equal(ledger.ts.add(sweepable).toString());
+    });
+});
@@ -0,0 +1,117 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey, Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { createHash } from "crypto";
+import { Oridion } from "../target/types/oridion";
+
+// Local validator tests: run with `npm run test:local` (builds with the `localnet` feature
+// so the program manager is tests/keys/manager.json, which is also the provider wallet).
+// No passing run of this suite is recorded yet (see README).
+
+export const provider = anchor.AnchorProvider.env();
+anchor.setProvider(provider);
+
+export const program = anchor.workspace.Oridion as Program<Oridion>;
+export const manager = (provider.wallet as anchor.Wallet).payer;
+
+export const NATIVE_MINT_SENTINEL = PublicKey.default;
+const UNIVERSE_PDA_SEED = Buffer.from("_x_ORIDION_x_");
+const PLANET_PDA_SEED_PRE = Buffer.from("_PLA_");
+const PLANET_PDA_SEED_POST = Buffer.from("_NET_");
+const STAR_SEED_PRE = Buffer.from("_ST_");
+const STAR_SEED_POST = Buffer.from("_AR_");
+const STAKE_SEED = Buffer.from("_ST_AKE_");
+const COMET_SEED = Buffer.from("_CO_MET_");
+
+const sha256 = (...parts: Buffer[]): Buffer => {
+    const hash = createHash("sha256");
+    parts.forEach((part) => hash.update(part));
+    return hash.digest();
+};
+
+const u64le = (value: number | anchor.BN): Buffer => new anchor.BN(value).toArrayLike(Buffer, "le", 8);
+
+export const universePda = (mint: PublicKey): PublicKey =>
+    PublicKey.findProgramAddressSync([UNIVERSE_PDA_SEED, mint.toBuffer()], program.programId)[0];
+
+export const planetPda = (name: string, mint: PublicKey): PublicKey =>
+    PublicKey.findProgramAddressSync(
+        [PLANET_PDA_SEED_PRE, sha256(mint.toBuffer(), Buffer.from(name)), PLANET_PDA_SEED_POST],
+        program.programId
+    )[0];
+
+export const starPda = (id: number | anchor.BN, mint: PublicKey): PublicKey =>
+    PublicKey.findProgramAddressSync(
+        [STAR_SEED_PRE, sha256(mint.toBuffer(), u64le(id)), STAR_SEED_POST],
+        program.programId
+    )[0];
+
+export const stakePda = (planet: PublicKey): PublicKey =>
+    PublicKey.findProgramAddressSync([STAKE_SEED, planet.toBuffer()], program.programId)[0];
+
+export const cometPda = (universe: PublicKey, id: number | anchor.BN): PublicKey =>
+    PublicKey.findProgramAddressSync([COMET_SEED, universe.toBuffer(), u64le(id)], program.programId)[0];
+
+// Comet ids only need to be unique per universe
+let lastCometId = Date.now();
+export const nextCometId = (): number => ++lastCometId;
+
+export const sleep = (ms: number) => new Promise((resolve) => setTimeout(resolve, ms));
+
+export const airdrop = async (to: PublicKey, sol = 10): Promise<void> => {
+    const signature = await provider.connection.requestAirdrop(to, sol * LAMPORTS_PER_SOL);
+    const latest = await provider.connection.getLatestBlockhash();
+    await provider.connection.confirmTransaction({ signature, ...latest }, "confirmed");
+};
+
+export const fundedKeypair = async (sol = 10): Promise<Keypair> => {
+    const keypair = Keypair.generate();
+    await airdrop(keypair.publicKey, sol);
+    return keypair;
+};
+
+export const balance = (account: PublicKey): Promise<number> => provider.connection.getBalance(account, "confirmed");
+
+// Creates the universe of a mint once (universes are one per mint and never closed)
+export const ensureUniverse = async (mint: PublicKey): Promise<PublicKey> => {
+    const universe = universePda(mint);
+    if ((await provider.connection.getAccountInfo(universe)) === null) {
+        await program.methods.bang(mint).accountsPartial({ universe, creator: manager.publicKey }).rpc();
+    }
+    return universe;
+};
+
+// Creates a planet of a universe once
+export const ensurePlanet = async (name: string, mint: PublicKey): Promise<PublicKey> => {
+    const universe = await ensureUniverse(mint);
+    const planet = planetPda(name, mint);
+    if ((await provider.connection.getAccountInfo(planet)) === null) {
+        await program.methods.createPlanet(name).accountsPartial({ planet, universe, creator: manager.publicKey }).rpc();
+    }
+    return planet;
+};
+
+// Deposits lamports from a wallet into a SOL universe planet
+export const depositSol = async (planet: PublicKey, lamports: number, creator: Keypair): Promise<void> => {
+    const universe = universePda(NATIVE_MINT_SENTINEL);
+    await program.methods
+        .newComet(new anchor.BN(lamports))
+        .accountsPartial({ creator: creator.publicKey, universe, planet, oracle: null })
+        .signers([creator])
+        .rpc();
+};
+
+// Asserts the promise fails with the given program error code name
+export const expectError = async (promise: Promise<unknown>, code: string): Promise<void> => {
+    try {
+        await promise;
+    } catch (err) {
+        const anchorCode = (err as anchor.AnchorError)?.error?.errorCode?.code;
+        const logs: string[] = (err as { logs?: string[] })?.logs ?? [];
+        if (anchorCode === code || logs.some((line) => line.includes(code)) || String(err).includes(code)) {
+            return;
+        }
+        throw new Error(`expected ${code}, got ${err}`);
+    }
+    throw new Error(`expected ${code}, transaction succeeded`);
+};
@@ -0,0 +1,67 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+import { MockTarget } from "../target/types/mock_target";
+import { PartnerExample } from "../target/types/partner_example";
+import { program, provider, manager, ensurePlanet, depositSol, fundedKeypair, balance, expectError, universePda, NATIVE_MINT_SENTINEL } from "./helpers";
+
+// withdraw_and_invoke pays out, then calls an allowlisted program (mock target) that reads the
+// destination balance. The destination must be a remaining account (the planet and universe are
+// appended by the program). Programs outside the allowlist are rejected.
+describe("withdraw and invoke", () => {
+    const target = anchor.workspace.MockTarget as Program<MockTarget>;
+    const partner = anchor.workspace.PartnerExample as Program<PartnerExample>;
+    const universe = universePda(NATIVE_MINT_SENTINEL);
+    const [allowlist] = PublicKey.findProgramAddressSync([Buffer.from("_ALLOW_LIST_"), universe.toBuffer()], program.programId);
+    const withdrawLamports = 50_000_000;
+    let planet: PublicKey;
+
+    const receiveData = async (destination: PublicKey, expected: number): Promise<Buffer> =>
+        (await target.methods.receive(new anchor.BN(expected)).accounts({ destination }).instruction()).data;
+    const withdrawAndInvoke = async (destination: PublicKey, targetProgram: PublicKey, data: Buffer, callbackAccount = destination) =>
+        program.methods
+            .withdrawAndInvoke(new anchor.BN(withdrawLamports), data)
+            .accountsPartial({ universe, allowlist, fromPlanet: planet, destination, targetProgram, manager: manager.publicKey, oracle: null })
+            .remainingAccounts([{ pubkey: callbackAccount, isSigner: false, isWritable: false }])
+            .rpc();
+
+    before(async () => {
+        planet = await ensurePlanet("INVOKE", NATIVE_MINT_SENTINEL);
+        await depositSol(planet, 500_000_000, await fundedKeypair(2));
+        if ((await provider.connection.getAccountInfo(allowlist)) === null) {
+            await program.methods.createAllowlist().accountsPartial({ allowlist, universe, creator: manager.publicKey }).rpc();
+        }
+        const programs = (await program.account.allowlist.fetch(allowlist)).pg as PublicKey[];
+        if (!programs.some((allowed) => allowed.equals(target.programId))) {
+            await program.methods.allowProgram(target.programId).accountsPartial({ allowlist, universe, creator: manager.publicKey }).rpc();
+        }
+    });
+
+    it("pays out and calls the allowlisted program", async () => {
+        const destination = (await fundedKeypair(1)).publicKey;
+        const before = await balance(destination);
+        await withdrawAndInvoke(destination, target.programId, await receiveData(destination, before + withdrawLamports));
+        expect((await balance(destination)) - before).to.equal(withdrawLamports);
+    });
+
+    it("rejects a callback that does not see the destination", async () => {
+        const destination = (await fundedKeypair(1)).publicKey;
+        const other = (await fundedKeypair(1)).publicKey;
+        const before = await balance(destination);
+        await expectError(withdrawAndInvoke(destination, target.programId, await receiveData(other, 0), other), "InvokeDestinationMissing");
+        expect(await balance(destination)).to.equal(before);
+    });
+
+    it("reverts the payout when the callback fails", async () => {
+        const destination = (await fundedKeypair(1)).publicKey;
+        const before = await balance(destination);
+        await expectError(withdrawAndInvoke(destination, target.programId, await receiveData(destination, before + withdrawLamports + 1)), "NotReceived");
+        expect(await balance(destination)).to.equal(before);
+    });
+
+    it("rejects a program outside the allowlist", async () => {
+        const destination = Keypair.generate().publicKey;
+        await expectError(withdrawAndInvoke(destination, partner.programId, Buffer.alloc(8)), "ProgramNotAllowlisted");
+    });
+});
@@ -0,0 +1,48 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram, Transaction, SYSVAR_RENT_PUBKEY } from "@solana/web3.js";
+import { expect } from "chai";
+import { PartnerExample } from "../target/types/partner_example";
+import { program, provider, manager, ensurePlanet, balance, universePda, NATIVE_MINT_SENTINEL } from "./helpers";
+
+// A partner program deposits from its own PDA by CPI into new_comet (`cpi` feature),
+// passing the documented account order: creator, universe, planet, system_program, rent, oracle.
+describe("partner cpi deposit", () => {
+    const partner = anchor.workspace.PartnerExample as Program<PartnerExample>;
+    const [vault] = PublicKey.findProgramAddressSync([Buffer.from("_PARTNER_VAULT_")], partner.programId);
+    const universe = universePda(NATIVE_MINT_SENTINEL);
+    let planet: PublicKey;
+
+    before(async () => {
+        planet = await ensurePlanet("PARTNER", NATIVE_MINT_SENTINEL);
+        await provider.sendAndConfirm(
+            new Transaction().add(SystemProgram.transfer({ fromPubkey: manager.publicKey, toPubkey: vault, lamports: 1_000_000_000 }))
+        );
+    });
+
+    it("deposits from the partner vault PDA", async () => {
+        const deposit = 250_000_000;
+        const ledgerBefore = await program.account.universe.fetch(universe);
+        const vaultBefore = await balance(vault);
+        const planetBefore = await balance(planet);
+
+        await partner.methods
+            .deposit(new anchor.BN(deposit))
+            .accountsStrict({
+                vault,
+                universe,
+                planet,
+                oridionProgram: program.programId,
+                systemProgram: SystemProgram.programId,
+                rent: SYSVAR_RENT_PUBKEY,
+                oracle: null,
+            })
+            .rpc();
+
+        const ledgerAfter = await program.account.universe.fetch(universe);
+        expect(vaultBefore - (await balance(vault))).to.equal(deposit);
+        expect((await balance(planet)) - planetBefore).to.equal(deposit);
+        expect(ledgerAfter.td.sub(ledgerBefore.td).toNumber()).to.equal(deposit);
+        expect((await program.account.planet.fetch(planet)).visits.toNumber()).to.be.greaterThan(0);
+    });
+});
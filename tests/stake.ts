@@ -0,0 +1,87 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, SystemProgram, StakeProgram, Transaction, SYSVAR_CLOCK_PUBKEY, SYSVAR_STAKE_HISTORY_PUBKEY } from "@solana/web3.js";
+import { expect } from "chai";
+import { program, provider, manager, ensurePlanet, depositSol, stakePda, fundedKeypair, balance, expectError, NATIVE_MINT_SENTINEL } from "./helpers";
+
+const STAKE_CONFIG_ID = new PublicKey("StakeConfig11111111111111111111111111111111");
+
+// Native staking of planet lamports. The stake is deactivated in the epoch it was
+// activated, which makes it withdrawable right away (see [test.validator] in Anchor.toml).
+describe("planet staking", () => {
+    const connection = provider.connection;
+    let universe: PublicKey;
+    let planet: PublicKey;
+    let stakeAccount: PublicKey;
+    let voteAccount: PublicKey;
+    const principal = 2_000_000_000;
+
+    const stakeAccounts = () => ({
+        universe,
+        planet,
+        stakeAccount,
+        manager: manager.publicKey,
+        clock: SYSVAR_CLOCK_PUBKEY,
+        stakeHistory: SYSVAR_STAKE_HISTORY_PUBKEY,
+        stakeProgram: StakeProgram.programId,
+    });
+
+    before(async () => {
+        planet = await ensurePlanet("STAKE", NATIVE_MINT_SENTINEL);
+        universe = (await program.account.planet.fetch(planet)).universe;
+        stakeAccount = stakePda(planet);
+        voteAccount = new PublicKey((await connection.getVoteAccounts()).current[0].votePubkey);
+        await depositSol(planet, 5_000_000_000, await fundedKeypair(10));
+        await program.methods.updateStakeFraction(5_000).accountsPartial({ universe, creator: manager.publicKey }).rpc();
+    });
+
+    it("rejects more than the stake fraction of the planet", async () => {
+        await expectError(
+            program.methods
+                .stakePlanet(new anchor.BN(4_000_000_000))
+                .accountsPartial({ ...stakeAccounts(), voteAccount, stakeConfig: STAKE_CONFIG_ID, systemProgram: SystemProgram.programId })
+                .rpc(),
+            "StakeAmountInvalid"
+        );
+    });
+
+    it("stakes even when lamports were sent to the stake address first", async () => {
+        // Griefing attempt: a fixed PDA address that already holds lamports
+        const griefer = await fundedKeypair(1);
+        await provider.sendAndConfirm(new Transaction().add(SystemProgram.transfer({ fromPubkey: griefer.publicKey, toPubkey: stakeAccount, lamports: 1 })), [griefer]);
+
+        const planetBefore = await balance(planet);
+        await program.methods
+            .stakePlanet(new anchor.BN(principal))
+            .accountsPartial({ ...stakeAccounts(), voteAccount, stakeConfig: STAKE_CONFIG_ID, systemProgram: SystemProgram.programId })
+            .rpc();
+
+        expect(planetBefore - (await balance(planet))).to.equal(principal);
+        expect((await connection.getAccountInfo(stakeAccount, "confirmed"))!.owner.toBase58()).to.equal(StakeProgram.programId.toBase58());
+        expect((await program.account.planet.fetch(planet)).sk.toNumber()).to.equal(principal);
+    });
+
+    it("rejects a second stake of the same planet", async () => {
+        await expectError(
+            program.methods
+                .stakePlanet(new anchor.BN(1_000_000))
+                .accountsPartial({ ...stakeAccounts(), voteAccount, stakeConfig: STAKE_CONFIG_ID, systemProgram: SystemProgram.programId })
+                .rpc(),
+            "PlanetAlreadyStaked"
+        );
+    });
+
+    it("deactivates and withdraws the principal back to the planet", async () => {
+        const before = await program.account.universe.fetch(universe);
+        const planetBefore = await balance(planet);
+        await program.methods.deactivatePlanetStake().accountsPartial({ universe, planet, stakeAccount, manager: manager.publicKey, clock: SYSVAR_CLOCK_PUBKEY, stakeProgram: StakeProgram.programId }).rpc();
+        await program.methods.withdrawPlanetStake().accountsPartial(stakeAccounts()).rpc();
+
+        const after = await program.account.universe.fetch(universe);
+        expect((await balance(planet)) - planetBefore).to.equal(principal);
+        expect(await connection.getAccountInfo(stakeAccount, "confirmed")).to.be.null;
+        expect((await program.account.planet.fetch(planet)).sk.toNumber()).to.equal(0);
+        expect(after.sk.sub(before.sk).toNumber()).to.equal(-principal);
+        expect(after.rw.sub(before.rw).toNumber()).to.equal(0); //the donated lamport covered part of the rent
+        expect(after.sl.sub(before.sl).toNumber()).to.equal(0);
+    });
+});
@@ -0,0 +1,300 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey, Keypair } from "@solana/web3.js";
+import {
+    TOKEN_PROGRAM_ID,
+    TOKEN_2022_PROGRAM_ID,
+    createMint,
+    getAssociatedTokenAddressSync,
+    getOrCreateAssociatedTokenAccount,
+    createAssociatedTokenAccountIdempotent,
+    mintTo,
+    transfer,
+    getAccount,
+} from "@solana/spl-token";
+import { expect } from "chai";
+import { program, provider, manager, ensurePlanet, starPda, cometPda, nextCometId, fundedKeypair, expectError, sleep } from "./helpers";
+
+// Token mode against a local SPL mint: planet vaults, token comets, planet and star hops,
+// pre-created star vaults, stranded token star recovery, token fees, the token ledger and denominations.
+describe("token mode", () => {
+    const connection = provider.connection;
+    const decimals = 6;
+    let mint: PublicKey;
+    let universe: PublicKey;
+    let alpha: PublicKey;
+    let beta: PublicKey;
+    let alphaVault: PublicKey;
+    let betaVault: PublicKey;
+    let user: Keypair;
+    let userSource: PublicKey;
+    let comet: PublicKey;
+
+    const vault = (owner: PublicKey): PublicKey => getAssociatedTokenAddressSync(mint, owner, true, TOKEN_PROGRAM_ID);
+    const tokens = async (account: PublicKey): Promise<bigint> => (await getAccount(connection, account, "confirmed")).amount;
+    const nextStars = async (count: number): Promise<PublicKey[]> => {
+        const sc = (await program.account.universe.fetch(universe)).sc as anchor.BN;
+        return [...Array(count).keys()].map((offset) => starPda(sc.addn(offset), mint));
+    };
+    const starVaultAccounts = (stars: PublicKey[]) => {
+        const names = ["starOne", "starTwo", "starThree"];
+        return Object.fromEntries(stars.flatMap((star, i) => [[names[i], star], [`${names[i]}Vault`, vault(star)]]));
+    };
+
+    before(async () => {
+        user = await fundedKeypair();
+        mint = await createMint(connection, manager, manager.publicKey, null, decimals);
+        alpha = await ensurePlanet("ALPHA", mint);
+        beta = await ensurePlanet("BETA", mint);
+        universe = (await program.account.planet.fetch(alpha)).universe;
+        alphaVault = vault(alpha);
+        betaVault = vault(beta);
+        for (const planet of [alpha, beta]) {
+            await program.methods.createPlanetVault().accountsPartial({ universe, planet, mint, vault: vault(planet), creator: manager.publicKey, tokenProgram: TOKEN_PROGRAM_ID }).rpc();
+        }
+        await program.methods.createTreasuryVault().accountsPartial({ universe, mint, vault: vault(universe), creator: manager.publicKey, tokenProgram: TOKEN_PROGRAM_ID }).rpc();
+        await program.methods.updateFee(0, 0, 0, 0, 0).accountsPartial({ universe, creator: manager.publicKey }).rpc();
+        userSource = (await getOrCreateAssociatedTokenAccount(connection, manager, mint, user.publicKey)).address;
+        await mintTo(connection, manager, mint, userSource, manager, 10_000_000);
+    });
+
+    it("creates a planet vault that already exists through the ATA program", async () => {
+        await program.methods.createPlanetVault().accountsPartial({ universe, planet: alpha, mint, vault: alphaVault, creator: manager.publicKey, tokenProgram: TOKEN_PROGRAM_ID }).rpc();
+    });
+
+    it("deposits tokens into a planet vault with a hop package", async () => {
+        const id = nextCometId();
+        comet = cometPda(universe, id);
+        await program.methods
+            .newTokenCometWithHops(new anchor.BN(4_000_000), { id: new anchor.BN(id), planet: 1, starTwo: 2, starThree: 1 })
+            .accountsPartial({
+                deposit: { universe, creator: user.publicKey, planet: alpha, mint, source: userSource, planetVault: alphaVault, treasuryVault: vault(universe), tokenProgram: TOKEN_PROGRAM_ID },
+                payer: user.publicKey,
+                comet,
+            })
+            .signers([user])
+            .rpc();
+        expect(await tokens(alphaVault)).to.equal(4_000_000n);
+    });
+
+    it("hops tokens between planets", async () => {
+        await program.methods
+            .tokenPlanetHop(new anchor.BN(1_000_000))
+            .accountsPartial({ universe, comet, mint, toPlanet: beta, toVault: betaVault, fromPlanet: alpha, fromVault: alphaVault, manager: manager.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+            .rpc();
+        expect(await tokens(alphaVault)).to.equal(3_000_000n);
+        expect(await tokens(betaVault)).to.equal(1_000_000n);
+    });
+
+    it("star hops through pre-created empty star vaults", async () => {
+        const stars = await nextStars(2);
+        // Anyone can create the star associated token accounts before the hop
+        const attacker = await fundedKeypair();
+        for (const star of stars) {
+            await createAssociatedTokenAccountIdempotent(connection, attacker, mint, star, {}, TOKEN_PROGRAM_ID, undefined, true);
+        }
+        await program.methods
+            .tokenStarHopTwoStart(new anchor.BN(1_000_000))
+            .accountsPartial({ universe, comet, mint, fromPlanet: alpha, fromVault: alphaVault, ...starVaultAccounts(stars), manager: manager.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+            .rpc();
+        const amounts = await Promise.all(stars.map((star) => tokens(vault(star))));
+        expect(amounts[0] + amounts[1]).to.equal(1_000_000n);
+
+        await program.methods
+            .tokenStarHopTwoEnd(new anchor.BN(1_000_000))
+            .accountsPartial({ universe, mint, toPlanet: beta, toVault: betaVault, ...starVaultAccounts(stars), manager: manager.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+            .rpc();
+        expect(await tokens(betaVault)).to.equal(2_000_000n);
+        for (const star of stars) {
+            expect(await connection.getAccountInfo(star, "confirmed")).to.be.null;
+            expect(await connection.getAccountInfo(vault(star), "confirmed")).to.be.null;
+        }
+    });
+
+    it("rejects a star hop into a pre-funded star vault", async () => {
+        const stars = await nextStars(3);
+        const donor = await fundedKeypair();
+        const donorSource = (await getOrCreateAssociatedTokenAccount(connection, donor, mint, donor.publicKey)).address;
+        await mintTo(connection, manager, mint, donorSource, manager, 1);
+        const starVault = await createAssociatedTokenAccountIdempotent(connection, donor, mint, stars[2], {}, TOKEN_PROGRAM_ID, undefined, true);
+        await transfer(connection, donor, donorSource, starVault, donor, 1);
+
+        await expectError(
+            program.methods
+                .tokenStarHopThreeStart(new anchor.BN(900_000))
+                .accountsPartial({ universe, comet, mint, fromPlanet: alpha, fromVault: alphaVault, ...starVaultAccounts(stars), manager: manager.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+                .rpc(),
+            "StarVaultNotEmpty"
+        );
+        expect(await tokens(alphaVault)).to.equal(2_000_000n);
+    });
+
+    it("recovers stranded token stars to the origin planet", async () => {
+        await program.methods.updateStarTimeout(new anchor.BN(1)).accountsPartial({ universe, creator: manager.publicKey }).rpc();
+        const stars = await nextStars(2);
+        await program.methods
+            .tokenStarHopTwoStart(new anchor.BN(500_000))
+            .accountsPartial({ universe, comet, mint, fromPlanet: alpha, fromVault: alphaVault, ...starVaultAccounts(stars), manager: manager.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+            .rpc();
+        expect(await tokens(alphaVault)).to.equal(1_500_000n);
+
+        await sleep(2000);
+        for (const star of stars) {
+            await program.methods
+                .recoverTokenStar()
+                .accountsPartial({ universe, mint, toPlanet: alpha, toVault: alphaVault, star, starVault: vault(star), manager: manager.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+                .rpc();
+            expect(await connection.getAccountInfo(star, "confirmed")).to.be.null;
+        }
+        expect(await tokens(alphaVault)).to.equal(2_000_000n);
+    });
+
+    it("withdraws tokens to the owner's associated token account", async () => {
+        const recipient = Keypair.generate();
+        const destination = vault(recipient.publicKey);
+        await program.methods
+            .tokenWithdrawToAta(new anchor.BN(250_000))
+            .accountsPartial({ universe, mint, fromPlanet: beta, fromVault: betaVault, treasuryVault: vault(universe), destinationOwner: recipient.publicKey, destination, manager: manager.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+            .rpc();
+        expect(await tokens(destination)).to.equal(250_000n);
+        expect(await tokens(betaVault)).to.equal(1_750_000n);
+    });
+
+    it("balances the token ledger against the planet vaults", async () => {
+        const totals = await program.methods
+            .verifyTokenInvariants()
+            .accountsPartial({ universe, mint })
+            .remainingAccounts([alpha, alphaVault, beta, betaVault].map((pubkey) => ({ pubkey, isSigner: false, isWritable: false })))
+            .view();
+        expect(totals.planets.toString()).to.equal("3750000");
+        expect(totals.deposited.toString()).to.equal("4000000");
+        expect(totals.withdrawn.toString()).to.equal("250000");
+    });
+
+    it("charges token fees into the treasury vault and sweeps them", async () => {
+        await program.methods.updateFee(1_000, 0, 0, 0, 500).accountsPartial({ universe, creator: manager.publicKey }).rpc();
+        const before = await program.account.universe.fetch(universe);
+        await program.methods
+            .newTokenComet(new anchor.BN(100_000))
+            .accountsPartial({ universe, creator: user.publicKey, planet: alpha, mint, source: userSource, planetVault: alphaVault, treasuryVault: vault(universe), tokenProgram: TOKEN_PROGRAM_ID })
+            .signers([user])
+            .rpc();
+        const destination = (await getOrCreateAssociatedTokenAccount(connection, manager, mint, Keypair.generate().publicKey)).address;
+        await program.methods
+            .tokenWithdraw(new anchor.BN(10_000))
+            .accountsPartial({ universe, mint, fromPlanet: alpha, fromVault: alphaVault, treasuryVault: vault(universe), destination, manager: manager.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+            .rpc();
+        expect(await tokens(destination)).to.equal(9_500n);
+        expect(await tokens(vault(universe))).to.equal(1_500n);
+        const after = await program.account.universe.fetch(universe);
+        expect(after.td.sub(before.td).toNumber()).to.equal(100_000);
+        expect(after.tw.sub(before.tw).toNumber()).to.equal(9_500);
+        expect(after.tf.sub(before.tf).toNumber()).to.equal(1_500);
+
+        const sweep = (amount: number) =>
+            program.methods
+                .sweepTokenTreasury(new anchor.BN(amount))
+                .accountsPartial({ universe, mint, treasuryVault: vault(universe), destination, creator: manager.publicKey, tokenProgram: TOKEN_PROGRAM_ID })
+                .rpc();
+        await expectError(sweep(1_501), "SweepExceedsTreasury");
+        await sweep(1_500);
+        expect(await tokens(vault(universe))).to.equal(0n);
+        expect(await tokens(destination)).to.equal(11_000n);
+        await program.methods.updateFee(0, 0, 0, 0, 0).accountsPartial({ universe, creator: manager.publicKey }).rpc();
+    });
+
+    it("only accepts deposits of a universe denomination", async () => {
+        const deposit = (amount: number) =>
+            program.methods
+                .newTokenComet(new anchor.BN(amount))
+                .accountsPartial({ universe, creator: user.publicKey, planet: alpha, mint, source: userSource, planetVault: alphaVault, treasuryVault: vault(universe), tokenProgram: TOKEN_PROGRAM_ID })
+                .signers([user])
+                .rpc();
+        await expectError(
+            program.methods.updateDenominations([new anchor.BN(100_000), new anchor.BN(10_000)]).accountsPartial({ universe, creator: manager.publicKey }).rpc(),
+            "InvalidDenominations"
+        );
+        await program.methods.updateDenominations([new anchor.BN(10_000), new anchor.BN(100_000)]).accountsPartial({ universe, creator: manager.publicKey }).rpc();
+        await expectError(deposit(50_000), "DepositNotADenomination");
+        await deposit(10_000);
+        await program.methods.updateDenominations([]).accountsPartial({ universe, creator: manager.publicKey }).rpc();
+        await deposit(50_000);
+    });
+
+    it("keeps a planet whose vault still holds tokens", async () => {
+        const omega = await ensurePlanet("OMEGA", mint);
+        await program.methods.createPlanetVault().accountsPartial({ universe, planet: omega, mint, vault: vault(omega), creator: manager.publicKey, tokenProgram: TOKEN_PROGRAM_ID }).rpc();
+        await mintTo(connection, manager, mint, vault(omega), manager, 1);
+        await expectError(
+            program.methods.deletePlanet().accountsPartial({ planet: omega, universe, creator: manager.publicKey, mint: null, vault: null }).rpc(),
+            "PlanetVaultRequired"
+        );
+        await expectError(
+            program.methods.deletePlanet().accountsPartial({ planet: omega, universe, creator: manager.publicKey, mint, vault: vault(omega) }).rpc(),
+            "PlanetVaultNotEmpty"
+        );
+        await program.methods.closePlanet().accountsPartial({ planet: omega, universe, creator: manager.publicKey }).rpc();
+        await expectError(
+            program.methods
+                .retirePlanet()
+                .accountsPartial({ planet: omega, universe, creator: manager.publicKey, mint, vault: vault(omega) })
+                .remainingAccounts([{ pubkey: alpha, isSigner: false, isWritable: true }])
+                .rpc(),
+            "PlanetVaultNotEmpty"
+        );
+    });
+});
+
+// Star vaults of a Token-2022 mint without extensions close without a harvest
+describe("token mode with a plain Token-2022 mint", () => {
+    const connection = provider.connection;
+    let mint: PublicKey;
+    let universe: PublicKey;
+    let gamma: PublicKey;
+    let delta: PublicKey;
+    let comet: PublicKey;
+
+    const vault = (owner: PublicKey): PublicKey => getAssociatedTokenAddressSync(mint, owner, true, TOKEN_2022_PROGRAM_ID);
+    const tokens = async (account: PublicKey): Promise<bigint> =>
+        (await getAccount(connection, account, "confirmed", TOKEN_2022_PROGRAM_ID)).amount;
+
+    before(async () => {
+        mint = await createMint(connection, manager, manager.publicKey, null, 6, undefined, {}, TOKEN_2022_PROGRAM_ID);
+        gamma = await ensurePlanet("GAMMA", mint);
+        delta = await ensurePlanet("DELTA", mint);
+        universe = (await program.account.planet.fetch(gamma)).universe;
+        for (const planet of [gamma, delta]) {
+            await program.methods.createPlanetVault().accountsPartial({ universe, planet, mint, vault: vault(planet), creator: manager.publicKey, tokenProgram: TOKEN_2022_PROGRAM_ID }).rpc();
+        }
+        await program.methods.createTreasuryVault().accountsPartial({ universe, mint, vault: vault(universe), creator: manager.publicKey, tokenProgram: TOKEN_2022_PROGRAM_ID }).rpc();
+        await program.methods.updateFee(0, 0, 0, 0, 0).accountsPartial({ universe, creator: manager.publicKey }).rpc();
+        const source = await createAssociatedTokenAccountIdempotent(connection, manager, mint, manager.publicKey, {}, TOKEN_2022_PROGRAM_ID);
+        await mintTo(connection, manager, mint, source, manager, 2_000_000, [], undefined, TOKEN_2022_PROGRAM_ID);
+        const id = nextCometId();
+        comet = cometPda(universe, id);
+        await program.methods
+            .newTokenCometWithHops(new anchor.BN(2_000_000), { id: new anchor.BN(id), planet: 0, starTwo: 1, starThree: 0 })
+            .accountsPartial({
+                deposit: { universe, creator: manager.publicKey, planet: gamma, mint, source, planetVault: vault(gamma), treasuryVault: vault(universe), tokenProgram: TOKEN_2022_PROGRAM_ID },
+                payer: manager.publicKey,
+                comet,
+            })
+            .rpc();
+    });
+
+    it("star hops and closes the star vaults", async () => {
+        const sc = (await program.account.universe.fetch(universe)).sc as anchor.BN;
+        const [starOne, starTwo] = [starPda(sc, mint), starPda(sc.addn(1), mint)];
+        const stars = { starOne, starOneVault: vault(starOne), starTwo, starTwoVault: vault(starTwo) };
+        await program.methods
+            .tokenStarHopTwoStart(new anchor.BN(2_000_000))
+            .accountsPartial({ universe, comet, mint, fromPlanet: gamma, fromVault: vault(gamma), ...stars, manager: manager.publicKey, tokenProgram: TOKEN_2022_PROGRAM_ID })
+            .rpc();
+        await program.methods
+            .tokenStarHopTwoEnd(new anchor.BN(2_000_000))
+            .accountsPartial({ universe, mint, toPlanet: delta, toVault: vault(delta), ...stars, manager: manager.publicKey, tokenProgram: TOKEN_2022_PROGRAM_ID })
+            .rpc();
+        expect(await tokens(vault(delta))).to.equal(2_000_000n);
+        expect(await connection.getAccountInfo(stars.starOneVault, "confirmed")).to.be.null;
+        expect(await connection.getAccountInfo(stars.starTwoVault, "confirmed")).to.be.null;
+    });
+});